@@ -1,3 +1,4 @@
+import json
 import matplotlib.pyplot as plt
 import pandas as pd
 from matplotlib.ticker import ScalarFormatter
@@ -23,20 +24,41 @@ class Category(StrEnum):
     BONUS = "bonus"
 
 
-class Heuristic(StrEnum):
-    NONE = "no-heuristic"
-    SMALLEST_REGION_BY_EMPTY_CELLS = "smallest-region-by-empty-cells"
-    SMALLEST_REGION_FIRST = "smallest-region-first"
+@dataclass(frozen=True)
+class Heuristic:
+    name: str
+    description: str
+    tags: Tuple[str, ...] = ()
 
     @property
     def display_label(self) -> str:
-        match self:
-            case Heuristic.NONE:
-                return "No Heuristic"
-            case Heuristic.SMALLEST_REGION_BY_EMPTY_CELLS:
-                return "Smallest Region (Empty Cells)"
-            case Heuristic.SMALLEST_REGION_FIRST:
-                return "Smallest Region First"
+        return self.name.replace("-", " ").title()
+
+
+def load_heuristics(stats_directory: Path) -> List[Heuristic]:
+    # written by the benchmark binary from the heuristic registry
+    path = stats_directory / "heuristics.json"
+    if not path.exists():
+        return heuristics_from_csv_names(stats_directory)
+
+    with open(path) as f:
+        return [
+            Heuristic(entry["name"], entry["description"], tuple(entry["tags"]))
+            for entry in json.load(f)
+        ]
+
+
+def heuristics_from_csv_names(stats_directory: Path) -> List[Heuristic]:
+    # older runs only left "<category>_<name>.csv" files behind
+    names_per_category = [
+        {
+            path.stem.removeprefix(f"{category.value}_")
+            for path in stats_directory.glob(f"{category.value}_*.csv")
+        }
+        for category in Category
+    ]
+    names = set.intersection(*names_per_category)
+    return [Heuristic(name, "") for name in sorted(names)]
 
 
 @dataclass
@@ -57,7 +79,7 @@ class BenchmarkStats:
 def load_csv(
     stats_directory: Path, category: Category, heuristic: Heuristic
 ) -> pd.DataFrame:
-    path = stats_directory / f"{category.value}_{heuristic.value}.csv"
+    path = stats_directory / f"{category.value}_{heuristic.name}.csv"
     return pd.read_csv(path)
 
 
@@ -71,9 +93,10 @@ def compute_stats(df: pd.DataFrame, column: str) -> Stats:
 
 def load_all_stats(stats_directory: Path) -> List[BenchmarkStats]:
     results: List[BenchmarkStats] = []
+    heuristics = load_heuristics(stats_directory)
 
     for category in Category:
-        for heuristic in Heuristic:
+        for heuristic in heuristics:
             df = load_csv(stats_directory, category, heuristic)
 
             latency_stats = compute_stats(df, "duration_ns")
@@ -241,6 +264,8 @@ def print_summary(all_stats: List[BenchmarkStats]) -> None:
 
         for s in category_stats:
             print(f"\n{s.heuristic.display_label}:")
+            if s.heuristic.description:
+                print(f"  {s.heuristic.description}")
             print(
                 f"  Latency - Avg: {s.latency.avg:.2f}ms, "
                 f"P90: {s.latency.p90:.2f}ms, "
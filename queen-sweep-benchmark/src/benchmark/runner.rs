@@ -49,11 +49,7 @@ pub fn benchmark_levels(
 }
 
 fn benchmark_level(level: &LevelData, heuristic: Heuristic) -> BenchmarkResult {
-    let color_regions: Vec<Vec<u8>> = level
-        .regions
-        .iter()
-        .map(|row| row.iter().map(|&v| v).collect())
-        .collect();
+    let color_regions: Vec<Vec<u8>> = level.regions.iter().map(|row| row.to_vec()).collect();
 
     let heuristic_fn = heuristic.to_fn();
 
@@ -1,36 +1,39 @@
+use queen_sweep_core::heuristic::{self, HeuristicFn, HeuristicInfo};
+
+/// A registered heuristic, or none at all.
 #[derive(Debug, Clone, Copy)]
-pub enum Heuristic {
-    None,
-    SmallestRegionFirst,
-    SmallestRegionByEmptyCells,
-}
+pub struct Heuristic(Option<&'static HeuristicInfo>);
 
 impl Heuristic {
     pub fn name(&self) -> &'static str {
-        match self {
-            Heuristic::None => "no-heuristic",
-            Heuristic::SmallestRegionFirst => "smallest-region-first",
-            Heuristic::SmallestRegionByEmptyCells => "smallest-region-by-empty-cells",
+        match self.0 {
+            Some(info) => info.name,
+            None => "no-heuristic",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self.0 {
+            Some(info) => info.description,
+            None => "Explores placements in row-major order.",
         }
     }
 
+    pub fn tags(&self) -> &'static [&'static str] {
+        self.0.map_or(&[], |info| info.tags)
+    }
+
     pub fn all() -> Vec<Heuristic> {
-        vec![
-            Heuristic::None,
-            Heuristic::SmallestRegionFirst,
-            Heuristic::SmallestRegionByEmptyCells,
-        ]
+        std::iter::once(Heuristic(None))
+            .chain(
+                heuristic::registry()
+                    .iter()
+                    .map(|&info| Heuristic(Some(info))),
+            )
+            .collect()
     }
 
-    pub fn to_fn(&self) -> Option<queen_sweep_core::heuristic::HeuristicFn> {
-        match self {
-            Heuristic::None => None,
-            Heuristic::SmallestRegionFirst => {
-                Some(queen_sweep_core::heuristic::smallest_region_first)
-            }
-            Heuristic::SmallestRegionByEmptyCells => {
-                Some(queen_sweep_core::heuristic::smallest_region_by_empty_cells)
-            }
-        }
+    pub fn to_fn(self) -> Option<HeuristicFn> {
+        self.0.map(|info| info.func)
     }
 }
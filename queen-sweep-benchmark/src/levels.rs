@@ -6,9 +6,9 @@ use std::{
 
 use serde::Deserialize;
 
-const BASE_LEVEL_JSONL: &'static str = "data/base-levels.jsonl";
-const BONUS_LEVEL_JSONL: &'static str = "data/bonus-levels.jsonl";
-const COMMUNITY_LEVEL_JSONL: &'static str = "data/community-levels.jsonl";
+const BASE_LEVEL_JSONL: &str = "data/base-levels.jsonl";
+const BONUS_LEVEL_JSONL: &str = "data/bonus-levels.jsonl";
+const COMMUNITY_LEVEL_JSONL: &str = "data/community-levels.jsonl";
 
 #[allow(dead_code)]
 #[derive(Debug, Clone, Deserialize)]
@@ -19,6 +19,7 @@ pub struct LevelData {
     pub source: LevelSource,
 }
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum LevelSource {
@@ -47,6 +48,8 @@ fn load_level_data<P: AsRef<Path>>(path: P) -> Vec<LevelData> {
 
     let reader = BufReader::new(file);
 
+    // unreadable lines are skipped like invalid records
+    #[allow(clippy::lines_filter_map_ok)]
     let mut result: Vec<LevelData> = reader
         .lines()
         .filter_map(Result::ok)
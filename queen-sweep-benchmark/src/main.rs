@@ -2,6 +2,7 @@ mod benchmark;
 mod heuristic;
 #[allow(dead_code)]
 mod levels;
+mod tune;
 
 use std::{fs, path::Path};
 
@@ -11,12 +12,23 @@ use crate::{
     levels::{LevelData, load_base_levels, load_bonus_levels},
 };
 
-const STATS_DIR: &'static str = "stats";
+const STATS_DIR: &str = "stats";
+const TUNED_WEIGHTS_FILE: &str = "tuned-weights.json";
+const HEURISTICS_FILE: &str = "heuristics.json";
 
 fn main() {
     let stats_dir = Path::new(STATS_DIR);
     fs::create_dir_all(stats_dir).expect("Failed to create output directory");
 
+    if std::env::args().nth(1).as_deref() == Some("tune") {
+        let mut levels = load_base_levels();
+        levels.extend(load_bonus_levels());
+        tune::tune(&levels, &stats_dir.join(TUNED_WEIGHTS_FILE));
+        return;
+    }
+
+    write_heuristics(&stats_dir.join(HEURISTICS_FILE));
+
     benchmark("base", load_base_levels());
     benchmark("bonus", load_bonus_levels());
 }
@@ -34,3 +46,20 @@ fn benchmark(category: &'static str, levels: Vec<LevelData>) {
         write_to_csv(output_file, &result);
     }
 }
+
+/// Lists the benchmarked heuristics for `process_benchmark.py`.
+fn write_heuristics(path: &Path) {
+    let heuristics: Vec<serde_json::Value> = Heuristic::all()
+        .iter()
+        .map(|heuristic| {
+            serde_json::json!({
+                "name": heuristic.name(),
+                "description": heuristic.description(),
+                "tags": heuristic.tags(),
+            })
+        })
+        .collect();
+
+    let json = serde_json::to_string_pretty(&heuristics).expect("heuristics serialize to JSON");
+    fs::write(path, json).expect("Failed to write heuristic list");
+}
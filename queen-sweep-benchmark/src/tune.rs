@@ -0,0 +1,92 @@
+use std::{fs, path::Path};
+
+use queen_sweep_core::{
+    GameState, depth_first_search,
+    heuristic::{LinearHeuristic, LinearWeights},
+};
+use rayon::prelude::*;
+
+use crate::levels::LevelData;
+
+// every fifth level is held out for validation
+const VALIDATION_EVERY: usize = 5;
+const INITIAL_STEP: f32 = 0.5;
+const MIN_STEP: f32 = 1.0 / 64.0;
+
+/// Tunes `LinearWeights` by coordinate descent on the total steps taken over
+/// the training split, then writes them to `output_file` as JSON.
+pub fn tune(levels: &[LevelData], output_file: &Path) {
+    let (train, validation): (Vec<_>, Vec<_>) = levels
+        .iter()
+        .enumerate()
+        .partition(|(i, _)| i % VALIDATION_EVERY != 0);
+    let train: Vec<&LevelData> = train.into_iter().map(|(_, level)| level).collect();
+    let validation: Vec<&LevelData> = validation.into_iter().map(|(_, level)| level).collect();
+
+    println!(
+        "Tuning on {} levels, validating on {}",
+        train.len(),
+        validation.len()
+    );
+
+    let baseline = LinearWeights::default();
+    let mut best = baseline;
+    let mut best_steps = total_steps(&train, best);
+    println!("Baseline: {} steps", best_steps);
+
+    let mut step = INITIAL_STEP;
+    while step >= MIN_STEP {
+        let mut improved = false;
+
+        for feature in 0..LinearWeights::FEATURES {
+            for delta in [step, -step] {
+                let mut weights = best.to_array();
+                weights[feature] += delta;
+                let candidate = LinearWeights::from_array(weights);
+
+                let steps = total_steps(&train, candidate);
+                if steps < best_steps {
+                    best = candidate;
+                    best_steps = steps;
+                    improved = true;
+                }
+            }
+        }
+
+        println!("Step {}: {} steps with {:?}", step, best_steps, best);
+        if !improved {
+            step /= 2.0;
+        }
+    }
+
+    println!(
+        "Validation: {} steps tuned, {} steps baseline",
+        total_steps(&validation, best),
+        total_steps(&validation, baseline)
+    );
+
+    let json = serde_json::to_string_pretty(&best).expect("weights serialize to JSON");
+    fs::write(output_file, json).expect("Failed to write tuned weights");
+    println!("Wrote {}", output_file.display());
+
+    println!(
+        "\npub const TUNED_WEIGHTS: LinearWeights = LinearWeights {{\n    \
+         region_empty: {:?},\n    row_empty: {:?},\n    col_empty: {:?},\n    eliminated: {:?},\n}};",
+        best.region_empty, best.row_empty, best.col_empty, best.eliminated
+    );
+}
+
+fn total_steps(levels: &[&LevelData], weights: LinearWeights) -> usize {
+    levels
+        .par_iter()
+        .map(|level| {
+            let color_regions: Vec<Vec<u8>> =
+                level.regions.iter().map(|row| row.to_vec()).collect();
+            let game_state = GameState::try_from(color_regions)
+                .expect("error initializing gamestate")
+                .with_heuristic(LinearHeuristic::new(weights));
+
+            depth_first_search(game_state).1
+        })
+        .sum()
+}
@@ -0,0 +1,18 @@
+#[derive(Debug)]
+pub enum ClueError {
+    UnsolvedBoard { queens: usize, expected: usize },
+}
+
+impl std::fmt::Display for ClueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsolvedBoard { queens, expected } => {
+                write!(
+                    f,
+                    "Board is not solved: {} of {} queens placed",
+                    queens, expected
+                )
+            }
+        }
+    }
+}
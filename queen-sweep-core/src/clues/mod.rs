@@ -0,0 +1,116 @@
+mod errors;
+pub use errors::ClueError;
+
+use crate::{
+    Branching, GameState, SolveOptions, enumerate_solutions,
+    game_state::{ColorGrid, RegionId},
+    has_unique_solution,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Clue {
+    /// A queen shown from the start.
+    Queen(usize, usize),
+    /// A cell marked as blocked from the start.
+    Blocked(usize, usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClueKind {
+    Queens,
+    BlockedCells,
+}
+
+/// Applies `clues` to `state`, placing queens with `place_queen` and blocking
+/// cells with `block_cells`.
+pub fn apply_clues(state: &GameState, clues: &[Clue]) -> GameState {
+    clues.iter().fold(state.clone(), |state, &clue| match clue {
+        Clue::Queen(r, c) => state.place_queen(r, c),
+        Clue::Blocked(r, c) => state.block_cells(&[(r, c)]),
+    })
+}
+
+/// Finds clues of the given kind that leave `solution` as the only solution of
+/// its layout. The set is minimal in that no single clue can be dropped, though
+/// a smaller set may exist.
+pub fn minimal_clues(solution: &GameState, kind: ClueKind) -> Result<Vec<Clue>, ClueError> {
+    if !solution.is_goal_state() {
+        return Err(ClueError::UnsolvedBoard {
+            queens: solution.queen_positions().count(),
+            expected: solution.region_count() * solution.rules().queens_per_unit,
+        });
+    }
+
+    let empty = empty_board(solution);
+
+    let options = SolveOptions {
+        symmetry_pruning: false,
+        branching: Branching::MostConstrainedUnit,
+        ..SolveOptions::default()
+    };
+    let queens: Vec<(usize, usize)> = solution.queen_positions().collect();
+    let mut clues = Vec::new();
+
+    // add a clue that rules out a competing solution until none is left
+    loop {
+        let (solutions, _) = enumerate_solutions(apply_clues(&empty, &clues), &options, Some(2));
+        let Some(competing) = solutions
+            .iter()
+            .find(|s| s.queen_positions().any(|queen| !queens.contains(&queen)))
+        else {
+            break;
+        };
+
+        let clue = match kind {
+            ClueKind::Queens => {
+                let placed: Vec<(usize, usize)> = competing.queen_positions().collect();
+                let &(r, c) = queens
+                    .iter()
+                    .find(|queen| !placed.contains(queen))
+                    .expect("solutions differ in some queen");
+                Clue::Queen(r, c)
+            }
+            ClueKind::BlockedCells => {
+                let (r, c) = competing
+                    .queen_positions()
+                    .find(|queen| !queens.contains(queen))
+                    .expect("solutions differ in some queen");
+                Clue::Blocked(r, c)
+            }
+        };
+        clues.push(clue);
+    }
+
+    // later clues may make earlier ones redundant
+    let mut i = 0;
+    while i < clues.len() {
+        let mut fewer = clues.clone();
+        fewer.remove(i);
+
+        if has_unique_solution(apply_clues(&empty, &fewer)) {
+            clues = fewer;
+        } else {
+            i += 1;
+        }
+    }
+
+    clues.sort_unstable();
+    Ok(clues)
+}
+
+/// The board of `state` with every queen and blocked cell removed.
+fn empty_board(state: &GameState) -> GameState {
+    fn rebuild<T: RegionId>(state: &GameState, ids: &[T]) -> GameState {
+        let regions = ids.chunks(state.cols()).map(|row| row.to_vec()).collect();
+        GameState::from_color_regions_with_rules(regions, None, state.rules().clone())
+            .expect("layout of a valid board")
+    }
+
+    match state.colors() {
+        ColorGrid::Narrow(ids) => rebuild(state, ids),
+        ColorGrid::Wide(ids) => rebuild(state, ids),
+    }
+}
+
+#[cfg(test)]
+mod test;
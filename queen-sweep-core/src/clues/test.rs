@@ -0,0 +1,78 @@
+use crate::{
+    Clue, ClueError, ClueKind, GameState, apply_clues, count_solutions, depth_first_search,
+    has_unique_solution, heuristic::smallest_region_by_empty_cells, minimal_clues,
+};
+
+fn puzzle_11x11_multiple_solns() -> Vec<Vec<u8>> {
+    vec![
+        vec![0, 1, 1, 0, 2, 2, 3, 3, 4, 1, 1],
+        vec![1, 0, 1, 4, 0, 2, 5, 6, 4, 4, 1],
+        vec![1, 1, 0, 4, 4, 0, 7, 8, 6, 4, 4],
+        vec![0, 4, 4, 0, 9, 10, 0, 7, 8, 6, 3],
+        vec![2, 0, 4, 9, 0, 9, 10, 0, 7, 5, 3],
+        vec![2, 2, 0, 10, 9, 0, 9, 10, 0, 2, 2],
+        vec![3, 5, 7, 0, 10, 9, 0, 9, 4, 0, 2],
+        vec![3, 6, 8, 7, 0, 10, 9, 0, 4, 4, 0],
+        vec![4, 4, 6, 8, 7, 0, 4, 4, 0, 1, 1],
+        vec![1, 4, 4, 6, 5, 2, 0, 4, 1, 0, 1],
+        vec![1, 1, 4, 3, 3, 2, 2, 0, 1, 1, 0],
+    ]
+}
+
+fn solved_ambiguous_board() -> (GameState, GameState) {
+    let empty = GameState::from_color_regions(
+        puzzle_11x11_multiple_solns(),
+        Some(smallest_region_by_empty_cells),
+    )
+    .unwrap();
+    let (solution, _) = depth_first_search(empty.clone());
+    (empty, solution.unwrap())
+}
+
+#[test]
+fn test_minimal_clues_make_layout_unique() {
+    let (empty, solution) = solved_ambiguous_board();
+    assert!(count_solutions(empty.clone(), Some(2)) > 1);
+
+    for kind in [ClueKind::Queens, ClueKind::BlockedCells] {
+        let clues = minimal_clues(&solution, kind).unwrap();
+        assert!(!clues.is_empty());
+
+        let clued = apply_clues(&empty, &clues);
+        assert!(has_unique_solution(clued.clone()));
+        let (unique, _) = depth_first_search(clued);
+        assert_eq!(unique.unwrap().states(), solution.states());
+
+        // dropping any clue brings back another solution
+        for i in 0..clues.len() {
+            let mut fewer = clues.clone();
+            fewer.remove(i);
+            assert!(!has_unique_solution(apply_clues(&empty, &fewer)));
+        }
+
+        for clue in clues {
+            match (kind, clue) {
+                (ClueKind::Queens, Clue::Queen(r, c)) => {
+                    assert!(solution.queen_positions().any(|q| q == (r, c)))
+                }
+                (ClueKind::BlockedCells, Clue::Blocked(r, c)) => {
+                    assert!(solution.queen_positions().all(|q| q != (r, c)))
+                }
+                _ => panic!("unexpected clue {clue:?}"),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_minimal_clues_rejects_unsolved_board() {
+    let (empty, _) = solved_ambiguous_board();
+
+    assert!(matches!(
+        minimal_clues(&empty, ClueKind::Queens),
+        Err(ClueError::UnsolvedBoard {
+            queens: 0,
+            expected: 11
+        })
+    ));
+}
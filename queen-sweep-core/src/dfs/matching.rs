@@ -0,0 +1,144 @@
+use std::collections::VecDeque;
+
+use crate::{CellState, GameState};
+
+const UNMATCHED: usize = usize::MAX;
+
+/// Whether the open regions can still take their missing queens in distinct
+/// free row slots, and in distinct free column slots. By Hall's theorem every
+/// solvable state passes, so a failing state can be pruned.
+pub(crate) fn regions_match_lines(state: &GameState) -> bool {
+    let (rows, cols) = (state.rows(), state.cols());
+    let rules = state.rules();
+
+    let mut row_room: Vec<usize> = (0..rows).map(|r| rules.row_target(r)).collect();
+    let mut col_room: Vec<usize> = (0..cols).map(|c| rules.col_target(c)).collect();
+    let mut missing = vec![rules.queens_per_unit; state.region_count()];
+
+    for (r, c) in state.queen_positions() {
+        row_room[r] = row_room[r].saturating_sub(1);
+        col_room[c] = col_room[c].saturating_sub(1);
+        if let Some(color) = state.colors().region(r * cols + c) {
+            missing[color] = missing[color].saturating_sub(1);
+        }
+    }
+
+    let open: Vec<usize> = (0..state.region_count())
+        .filter(|&color| !state.colors_with_queens()[color] && missing[color] > 0)
+        .collect();
+
+    let fits = |room: &[usize], line_of: &dyn Fn(usize) -> usize| {
+        // one right vertex per queen a line still takes
+        let mut first_slot = Vec::with_capacity(room.len());
+        let mut slots = 0;
+        for &line_room in room {
+            first_slot.push(slots);
+            slots += line_room;
+        }
+
+        // one left vertex per queen a region is missing
+        let mut adjacency = Vec::new();
+        for &color in &open {
+            let mut lines: Vec<usize> = state
+                .region_index()
+                .cells(color)
+                .iter()
+                .filter(|&&idx| state.states()[idx] == CellState::Empty)
+                .map(|&idx| line_of(idx))
+                .collect();
+            lines.sort_unstable();
+            lines.dedup();
+
+            let neighbors: Vec<usize> = lines
+                .into_iter()
+                .flat_map(|line| first_slot[line]..first_slot[line] + room[line])
+                .collect();
+            adjacency.extend(std::iter::repeat_n(neighbors, missing[color]));
+        }
+
+        adjacency.len() <= slots && maximum_matching(&adjacency, slots) == adjacency.len()
+    };
+
+    fits(&row_room, &|idx| idx / cols) && fits(&col_room, &|idx| idx % cols)
+}
+
+/// Size of a maximum matching in the bipartite graph joining left vertex `i`
+/// to every right vertex in `adjacency[i]`, found with Hopcroft–Karp.
+pub(crate) fn maximum_matching(adjacency: &[Vec<usize>], right: usize) -> usize {
+    let left = adjacency.len();
+    let mut match_left = vec![UNMATCHED; left];
+    let mut match_right = vec![UNMATCHED; right];
+    let mut dist = vec![0; left];
+    let mut matched = 0;
+
+    // each phase augments along a maximal set of shortest disjoint paths
+    while layer(adjacency, &match_left, &match_right, &mut dist) {
+        for u in 0..left {
+            if match_left[u] == UNMATCHED
+                && augment(u, adjacency, &mut match_left, &mut match_right, &mut dist)
+            {
+                matched += 1;
+            }
+        }
+    }
+
+    matched
+}
+
+/// Breadth-first layering from the free left vertices. Returns whether an
+/// augmenting path exists.
+fn layer(
+    adjacency: &[Vec<usize>],
+    match_left: &[usize],
+    match_right: &[usize],
+    dist: &mut [usize],
+) -> bool {
+    let mut queue = VecDeque::new();
+    for (u, &matched) in match_left.iter().enumerate() {
+        if matched == UNMATCHED {
+            dist[u] = 0;
+            queue.push_back(u);
+        } else {
+            dist[u] = usize::MAX;
+        }
+    }
+
+    let mut found = false;
+    while let Some(u) = queue.pop_front() {
+        for &v in &adjacency[u] {
+            match match_right[v] {
+                UNMATCHED => found = true,
+                w if dist[w] == usize::MAX => {
+                    dist[w] = dist[u] + 1;
+                    queue.push_back(w);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    found
+}
+
+fn augment(
+    u: usize,
+    adjacency: &[Vec<usize>],
+    match_left: &mut [usize],
+    match_right: &mut [usize],
+    dist: &mut [usize],
+) -> bool {
+    for &v in &adjacency[u] {
+        let w = match_right[v];
+        let extends = w == UNMATCHED
+            || (dist[w] == dist[u] + 1 && augment(w, adjacency, match_left, match_right, dist));
+        if extends {
+            match_left[u] = v;
+            match_right[v] = u;
+            return true;
+        }
+    }
+
+    // dead end for the rest of this phase
+    dist[u] = usize::MAX;
+    false
+}
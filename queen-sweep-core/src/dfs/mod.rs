@@ -0,0 +1,256 @@
+mod matching;
+
+mod transposition;
+pub use transposition::{TranspositionConfig, TranspositionPolicy};
+
+use crate::{GameState, SymmetryGroup, game_state::compute_hash};
+use matching::regions_match_lines;
+use transposition::TranspositionTable;
+
+/// Which placements the search branches over at every state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Branching {
+    /// Every empty cell, in heuristic order. Different orders of the same
+    /// queens reach the same state, which the transposition table absorbs.
+    #[default]
+    AllCells,
+    /// Only the empty cells of the row, column or region with the fewest of
+    /// them, in heuristic order. With one queen per unit, every queen set is
+    /// reached at most once; with more, the transposition table still absorbs
+    /// repeats.
+    MostConstrainedUnit,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SolveOptions {
+    pub transposition: TranspositionConfig,
+    /// Treat states that are rotations or reflections of a visited state as visited.
+    /// Only sound when a single solution is wanted, see `enumerate_solutions`.
+    pub symmetry_pruning: bool,
+    /// Skip states whose open regions cannot be matched to distinct free rows
+    /// and columns. Stronger than the lookahead of `place_queen`, but costs a
+    /// bipartite matching per expanded state.
+    pub matching_pruning: bool,
+    pub branching: Branching,
+}
+
+impl Default for SolveOptions {
+    fn default() -> Self {
+        SolveOptions {
+            transposition: TranspositionConfig::default(),
+            symmetry_pruning: true,
+            matching_pruning: false,
+            branching: Branching::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SolveStats {
+    pub states_visited: usize,
+    pub transposition_hits: usize,
+    pub transposition_evictions: usize,
+    /// Expanded states none of whose placements led to a solution.
+    pub backtracks: usize,
+    /// States rejected by `SolveOptions::matching_pruning`.
+    pub matching_prunes: usize,
+    /// Number of placements tried at each search depth.
+    pub branching_profile: Vec<usize>,
+}
+
+impl SolveStats {
+    fn record_branching(&mut self, depth: usize, placements: usize) {
+        if self.branching_profile.len() <= depth {
+            self.branching_profile.resize(depth + 1, 0);
+        }
+        self.branching_profile[depth] += placements;
+    }
+}
+
+pub fn depth_first_search(game_state: GameState) -> (Option<GameState>, usize) {
+    let (solution, stats) = depth_first_search_with_options(game_state, &SolveOptions::default());
+    (solution, stats.states_visited)
+}
+
+pub fn depth_first_search_with_options(
+    game_state: GameState,
+    options: &SolveOptions,
+) -> (Option<GameState>, SolveStats) {
+    let mut search = Search::new(&game_state, options);
+    let solution = dfs_helper_with_counter(game_state, 0, &mut search);
+    (solution, search.finish())
+}
+
+/// Collects distinct solutions, stopping once `limit` of them have been found.
+///
+/// Symmetry pruning skips the mirror images of explored subtrees together with
+/// the solutions inside them, so disable it in `options` for a complete enumeration.
+pub fn enumerate_solutions(
+    game_state: GameState,
+    options: &SolveOptions,
+    limit: Option<usize>,
+) -> (Vec<GameState>, SolveStats) {
+    let mut search = Search::new(&game_state, options);
+    let mut solutions = Vec::new();
+    enumerate_helper(game_state, 0, &mut search, &mut solutions, limit);
+    (solutions, search.finish())
+}
+
+/// Counts solutions up to `limit`, with symmetry pruning disabled.
+pub fn count_solutions(game_state: GameState, limit: Option<usize>) -> usize {
+    // branching on a single unit keeps the exhaustive search small
+    let options = SolveOptions {
+        symmetry_pruning: false,
+        branching: Branching::MostConstrainedUnit,
+        ..SolveOptions::default()
+    };
+    enumerate_solutions(game_state, &options, limit).0.len()
+}
+
+#[inline]
+pub fn has_unique_solution(game_state: GameState) -> bool {
+    count_solutions(game_state, Some(2)) == 1
+}
+
+struct Search {
+    table: TranspositionTable,
+    symmetry: Option<SymmetryGroup>,
+    matching_pruning: bool,
+    branching: Branching,
+    stats: SolveStats,
+}
+
+impl Search {
+    fn new(game_state: &GameState, options: &SolveOptions) -> Self {
+        if let Some(heuristic) = game_state.heuristic() {
+            heuristic.reset();
+        }
+
+        let symmetry = options
+            .symmetry_pruning
+            .then(|| game_state.symmetry_group())
+            .filter(|group| !group.is_trivial());
+
+        Search {
+            table: TranspositionTable::new(&options.transposition, game_state),
+            symmetry,
+            matching_pruning: options.matching_pruning,
+            branching: options.branching,
+            stats: SolveStats::default(),
+        }
+    }
+
+    /// Returns `true` if `game_state`, or a symmetric image of it, was visited before.
+    fn already_visited(&mut self, game_state: &GameState, depth: usize) -> bool {
+        match &self.symmetry {
+            Some(group) => {
+                let canonical = group.canonicalize(game_state.states());
+                self.table
+                    .check_and_insert(compute_hash(&canonical), &canonical, depth)
+            }
+            None => self
+                .table
+                .check_and_insert(game_state.hash(), game_state.states(), depth),
+        }
+    }
+
+    /// Returns `true` if matching pruning is enabled and rules out `game_state`.
+    fn prune(&mut self, game_state: &GameState) -> bool {
+        let pruned = self.matching_pruning && !regions_match_lines(game_state);
+        if pruned {
+            self.stats.matching_prunes += 1;
+        }
+        pruned
+    }
+
+    fn finish(mut self) -> SolveStats {
+        self.stats.transposition_hits = self.table.hits();
+        self.stats.transposition_evictions = self.table.evictions();
+        self.stats
+    }
+}
+
+fn dfs_helper_with_counter(
+    game_state: GameState,
+    depth: usize,
+    search: &mut Search,
+) -> Option<GameState> {
+    search.stats.states_visited += 1;
+
+    if search.already_visited(&game_state, depth) {
+        return None;
+    }
+
+    if game_state.is_goal_state() {
+        return Some(game_state);
+    }
+
+    if search.prune(&game_state) {
+        return None;
+    }
+
+    let placements = match search.branching {
+        Branching::AllCells => game_state.valid_placements(),
+        Branching::MostConstrainedUnit => game_state.unit_placements(),
+    };
+    search.stats.record_branching(depth, placements.len());
+
+    for (r, c) in placements {
+        let new_state = game_state.place_queen(r, c);
+        if let Some(solution) = dfs_helper_with_counter(new_state, depth + 1, search) {
+            return Some(solution);
+        }
+    }
+
+    search.stats.backtracks += 1;
+    None
+}
+
+fn enumerate_helper(
+    game_state: GameState,
+    depth: usize,
+    search: &mut Search,
+    solutions: &mut Vec<GameState>,
+    limit: Option<usize>,
+) {
+    if limit.is_some_and(|limit| solutions.len() >= limit) {
+        return;
+    }
+
+    search.stats.states_visited += 1;
+
+    if search.already_visited(&game_state, depth) {
+        return;
+    }
+
+    if game_state.is_goal_state() {
+        // lossy tables may lead back to a solution that was already collected
+        if !solutions.contains(&game_state) {
+            solutions.push(game_state);
+        }
+        return;
+    }
+
+    if search.prune(&game_state) {
+        return;
+    }
+
+    let placements = match search.branching {
+        Branching::AllCells => game_state.valid_placements(),
+        Branching::MostConstrainedUnit => game_state.unit_placements(),
+    };
+    search.stats.record_branching(depth, placements.len());
+
+    let found_before = solutions.len();
+    for (r, c) in placements {
+        let new_state = game_state.place_queen(r, c);
+        enumerate_helper(new_state, depth + 1, search, solutions, limit);
+    }
+
+    if solutions.len() == found_before {
+        search.stats.backtracks += 1;
+    }
+}
+
+#[cfg(test)]
+mod test;
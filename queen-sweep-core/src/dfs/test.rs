@@ -0,0 +1,181 @@
+use super::matching::maximum_matching;
+use crate::fixtures::{puzzle_3x3_nosol, puzzle_8x8_sol};
+use crate::{
+    Adjacency, Branching, GameState, RuleSet, SolveOptions, TranspositionConfig,
+    TranspositionPolicy, depth_first_search, depth_first_search_with_options, enumerate_solutions,
+    heuristic,
+};
+
+fn options(policy: TranspositionPolicy, max_bytes: Option<usize>) -> SolveOptions {
+    SolveOptions {
+        transposition: TranspositionConfig { policy, max_bytes },
+        ..SolveOptions::default()
+    }
+}
+
+#[test]
+fn test_default_options_match_depth_first_search() {
+    let state = GameState::from_color_regions(puzzle_8x8_sol(), None).unwrap();
+
+    let (expected, steps) = depth_first_search(state.clone());
+    let (solution, stats) = depth_first_search_with_options(state, &SolveOptions::default());
+
+    assert_eq!(solution, expected);
+    assert_eq!(stats.states_visited, steps);
+}
+
+#[test]
+fn test_every_policy_finds_solution() {
+    let policies = [
+        TranspositionPolicy::Disabled,
+        TranspositionPolicy::Exact,
+        TranspositionPolicy::Hashes,
+        TranspositionPolicy::Lru,
+        TranspositionPolicy::DepthPreferred,
+    ];
+
+    for policy in policies {
+        for max_bytes in [None, Some(1024)] {
+            let state = GameState::from_color_regions(
+                puzzle_8x8_sol(),
+                Some(heuristic::smallest_region_by_empty_cells),
+            )
+            .unwrap();
+
+            let (solution, _) = depth_first_search_with_options(state, &options(policy, max_bytes));
+            assert!(solution.is_some_and(|s| s.is_goal_state()), "{policy:?}");
+        }
+
+        let state = GameState::from_color_regions(puzzle_3x3_nosol(), None).unwrap();
+        let (solution, _) = depth_first_search_with_options(state, &options(policy, None));
+        assert!(solution.is_none(), "{policy:?}");
+    }
+}
+
+#[test]
+fn test_transposition_counters() {
+    let state = GameState::from_color_regions(puzzle_8x8_sol(), None).unwrap();
+
+    let (_, disabled) = depth_first_search_with_options(
+        state.clone(),
+        &options(TranspositionPolicy::Disabled, None),
+    );
+    assert_eq!(disabled.transposition_hits, 0);
+    assert_eq!(disabled.transposition_evictions, 0);
+
+    let (_, exact) =
+        depth_first_search_with_options(state.clone(), &options(TranspositionPolicy::Exact, None));
+    assert!(exact.transposition_hits > 0);
+    assert_eq!(exact.transposition_evictions, 0);
+    assert!(exact.states_visited <= disabled.states_visited);
+
+    // room for a single entry forces constant eviction
+    let (_, lru) =
+        depth_first_search_with_options(state, &options(TranspositionPolicy::Lru, Some(64)));
+    assert!(lru.transposition_evictions > 0);
+}
+
+#[test]
+fn test_maximum_matching() {
+    // left 2 can only use right 1, which pushes left 0 onto right 0
+    let adjacency = vec![vec![0, 1], vec![0], vec![1]];
+    assert_eq!(maximum_matching(&adjacency, 2), 2);
+
+    let adjacency = vec![vec![0, 1], vec![1, 2], vec![0]];
+    assert_eq!(maximum_matching(&adjacency, 3), 3);
+}
+
+#[test]
+fn test_matching_pruning_keeps_solutions() {
+    let matching = SolveOptions {
+        symmetry_pruning: false,
+        matching_pruning: true,
+        ..SolveOptions::default()
+    };
+    let plain = SolveOptions {
+        matching_pruning: false,
+        ..matching
+    };
+
+    for regions in [puzzle_8x8_sol(), puzzle_3x3_nosol()] {
+        let state = GameState::try_from(regions).unwrap();
+        let (pruned, pruned_stats) = enumerate_solutions(state.clone(), &matching, None);
+        let (full, full_stats) = enumerate_solutions(state, &plain, None);
+
+        assert_eq!(pruned, full);
+        assert_eq!(full_stats.matching_prunes, 0);
+        assert!(pruned_stats.states_visited <= full_stats.states_visited);
+    }
+
+    // placements on the 3x3 board leave regions competing for the same free line
+    let state = GameState::try_from(puzzle_3x3_nosol()).unwrap();
+    let (_, stats) = enumerate_solutions(state, &matching, None);
+    assert!(stats.matching_prunes > 0);
+}
+
+#[test]
+fn test_unit_branching_partitions_search() {
+    let options = SolveOptions {
+        symmetry_pruning: false,
+        branching: Branching::MostConstrainedUnit,
+        ..SolveOptions::default()
+    };
+
+    let state = GameState::from_color_regions(
+        puzzle_8x8_sol(),
+        Some(heuristic::smallest_region_by_empty_cells),
+    )
+    .unwrap();
+    let (expected, _) = depth_first_search(state.clone());
+    let (solution, stats) = depth_first_search_with_options(state, &options);
+    assert_eq!(solution, expected);
+    // no queen set is reached twice
+    assert_eq!(stats.transposition_hits, 0);
+
+    let state = GameState::try_from(puzzle_3x3_nosol()).unwrap();
+    let (solution, _) = depth_first_search_with_options(state, &options);
+    assert!(solution.is_none());
+}
+
+#[test]
+fn test_unit_branching_finds_every_star_battle_solution() {
+    // 4x4 board of 2x2 regions, two queens per unit that may touch
+    let regions: Vec<Vec<u8>> = (0..4)
+        .map(|r| (0..4).map(|c| (r / 2 * 2 + c / 2) as u8).collect())
+        .collect();
+    let rules = RuleSet {
+        adjacency: Adjacency::None,
+        ..RuleSet::star_battle(2)
+    };
+    let state = GameState::from_color_regions_with_rules(regions, None, rules).unwrap();
+
+    let options = |branching| SolveOptions {
+        symmetry_pruning: false,
+        branching,
+        ..SolveOptions::default()
+    };
+    let solutions = |branching| {
+        let (solutions, stats) = enumerate_solutions(state.clone(), &options(branching), None);
+        let mut queens: Vec<Vec<(usize, usize)>> = solutions
+            .iter()
+            .map(|s| s.queen_positions().collect())
+            .collect();
+        queens.sort();
+        (queens, stats)
+    };
+
+    let (expected, all_cells) = solutions(Branching::AllCells);
+    assert_eq!(expected.len(), 56);
+    let (found, unit) = solutions(Branching::MostConstrainedUnit);
+    assert_eq!(found, expected);
+    // the two enumerations explore different trees, and with two queens per
+    // unit even unit branching reaches some queen sets twice
+    assert!(unit.states_visited < all_cells.states_visited);
+    assert!(unit.transposition_hits > 0);
+
+    for branching in [Branching::AllCells, Branching::MostConstrainedUnit] {
+        let (solution, _) = depth_first_search_with_options(state.clone(), &options(branching));
+        let queens: Vec<(usize, usize)> = solution.unwrap().queen_positions().collect();
+        assert!(expected.contains(&queens));
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::{CellState, GameState};
+
+// rough per-entry footprints, including hash table overhead
+const HASH_ENTRY_BYTES: usize = 16;
+// hash key, bucket and boxed slice pointer of an `Exact` entry, plus the
+// table's control byte; the cells themselves are added per board
+const EXACT_ENTRY_OVERHEAD: usize =
+    size_of::<u64>() + size_of::<Vec<Box<[CellState]>>>() + size_of::<Box<[CellState]>>() + 1;
+const LRU_ENTRY_BYTES: usize = 64;
+const DEPTH_SLOT_BYTES: usize = size_of::<Option<DepthSlot>>();
+
+/// Budget used by `DepthPreferred` when no `max_bytes` is given.
+const DEFAULT_DEPTH_TABLE_BYTES: usize = 16 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TranspositionPolicy {
+    /// Never record visited states.
+    Disabled,
+    /// Keep a full copy of every visited state. Collision free.
+    #[default]
+    Exact,
+    /// Keep only the 64-bit hash of every visited state.
+    Hashes,
+    /// Keep hashes, evicting the least recently probed entry when full.
+    Lru,
+    /// Fixed-size hash slots where shallower states replace deeper ones.
+    DepthPreferred,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TranspositionConfig {
+    pub policy: TranspositionPolicy,
+    /// Upper bound on table memory in bytes.
+    /// `Exact` and `Hashes` stop recording once the budget is reached,
+    /// `Lru` and `DepthPreferred` start replacing entries.
+    pub max_bytes: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DepthSlot {
+    hash: u64,
+    depth: usize,
+}
+
+enum Store {
+    Disabled,
+    // buckets of full states keyed by their hash
+    Exact(HashMap<u64, Vec<Box<[CellState]>>>),
+    Hashes(HashSet<u64>),
+    Lru {
+        tick: u64,
+        last_used: HashMap<u64, u64>,
+        order: BTreeMap<u64, u64>,
+    },
+    DepthPreferred(Vec<Option<DepthSlot>>),
+}
+
+pub(crate) struct TranspositionTable {
+    store: Store,
+    len: usize,
+    capacity: usize,
+    hits: usize,
+    evictions: usize,
+}
+
+impl TranspositionTable {
+    /// Sizes the table for states of `board`.
+    pub fn new(config: &TranspositionConfig, board: &GameState) -> Self {
+        let entries = |entry_bytes: usize| match config.max_bytes {
+            Some(bytes) => bytes / entry_bytes,
+            None => usize::MAX,
+        };
+
+        let (store, capacity) = match config.policy {
+            TranspositionPolicy::Disabled => (Store::Disabled, 0),
+            TranspositionPolicy::Exact => {
+                let entry_bytes = size_of_val(board.states()) + EXACT_ENTRY_OVERHEAD;
+                (Store::Exact(HashMap::new()), entries(entry_bytes))
+            }
+            TranspositionPolicy::Hashes => {
+                (Store::Hashes(HashSet::new()), entries(HASH_ENTRY_BYTES))
+            }
+            TranspositionPolicy::Lru => (
+                Store::Lru {
+                    tick: 0,
+                    last_used: HashMap::new(),
+                    order: BTreeMap::new(),
+                },
+                entries(LRU_ENTRY_BYTES),
+            ),
+            TranspositionPolicy::DepthPreferred => {
+                let bytes = config.max_bytes.unwrap_or(DEFAULT_DEPTH_TABLE_BYTES);
+                let slots = (bytes / DEPTH_SLOT_BYTES).max(1);
+                (Store::DepthPreferred(vec![None; slots]), slots)
+            }
+        };
+
+        TranspositionTable {
+            store,
+            len: 0,
+            capacity,
+            hits: 0,
+            evictions: 0,
+        }
+    }
+
+    #[inline]
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    #[inline]
+    pub fn evictions(&self) -> usize {
+        self.evictions
+    }
+
+    /// Returns `true` if `states` has been recorded before, otherwise records it.
+    /// `hash` must be the hash of `states`.
+    pub fn check_and_insert(&mut self, hash: u64, states: &[CellState], depth: usize) -> bool {
+        let capacity = self.capacity;
+        let len = self.len;
+
+        let seen = match &mut self.store {
+            Store::Disabled => false,
+            Store::Exact(seen) => {
+                let found = seen
+                    .get(&hash)
+                    .is_some_and(|bucket| bucket.iter().any(|recorded| **recorded == *states));
+                if !found && len < capacity {
+                    seen.entry(hash).or_default().push(states.into());
+                    self.len += 1;
+                }
+                found
+            }
+            Store::Hashes(seen) => {
+                let found = seen.contains(&hash);
+                if !found && len < capacity {
+                    seen.insert(hash);
+                    self.len += 1;
+                }
+                found
+            }
+            Store::Lru {
+                tick,
+                last_used,
+                order,
+            } => {
+                *tick += 1;
+
+                if let Some(used) = last_used.get_mut(&hash) {
+                    order.remove(used);
+                    order.insert(*tick, hash);
+                    *used = *tick;
+                    true
+                } else {
+                    if last_used.len() >= capacity
+                        && let Some((_, oldest)) = order.pop_first()
+                    {
+                        last_used.remove(&oldest);
+                        self.evictions += 1;
+                    }
+
+                    if last_used.len() < capacity {
+                        last_used.insert(hash, *tick);
+                        order.insert(*tick, hash);
+                    }
+                    false
+                }
+            }
+            Store::DepthPreferred(slots) => {
+                let idx = (hash % slots.len() as u64) as usize;
+                match slots[idx] {
+                    Some(slot) if slot.hash == hash => true,
+                    Some(slot) => {
+                        // shallower states root larger subtrees, so they are worth more
+                        if depth <= slot.depth {
+                            slots[idx] = Some(DepthSlot { hash, depth });
+                            self.evictions += 1;
+                        }
+                        false
+                    }
+                    None => {
+                        slots[idx] = Some(DepthSlot { hash, depth });
+                        false
+                    }
+                }
+            }
+        };
+
+        if seen {
+            self.hits += 1;
+        }
+        seen
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    GameState, SolveOptions, Technique, count_solutions, depth_first_search_with_options,
+    solve_logically,
+};
+
+const GUESS_WEIGHT: f32 = 10.0;
+const BACKTRACK_WEIGHT: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Difficulty {
+    /// Solved with single cells and line confinement alone.
+    Easy,
+    /// Needs N regions in N lines style deductions.
+    Medium,
+    /// Needs contradiction checks.
+    Hard,
+    /// The logical solver stalls and the rest has to be searched.
+    Expert,
+}
+
+#[derive(Debug, Clone)]
+pub struct DifficultyReport {
+    pub difficulty: Difficulty,
+    pub score: f32,
+    /// Number of solutions, counted up to 2.
+    pub solutions: usize,
+    pub techniques: BTreeMap<Technique, usize>,
+    pub solved_logically: bool,
+    /// Placements the search made after the logical solver stalled.
+    pub guesses: usize,
+    pub backtracks: usize,
+    pub branching_profile: Vec<usize>,
+}
+
+impl DifficultyReport {
+    #[inline]
+    pub fn is_unique(&self) -> bool {
+        self.solutions == 1
+    }
+
+    #[inline]
+    pub fn hardest_technique(&self) -> Option<Technique> {
+        self.techniques.keys().next_back().copied()
+    }
+}
+
+impl Technique {
+    #[inline]
+    fn weight(self) -> f32 {
+        match self {
+            Technique::SingleCell => 1.0,
+            Technique::LineConfinement => 2.0,
+            Technique::RegionLineSets => 5.0,
+            Technique::Contradiction => 8.0,
+        }
+    }
+}
+
+/// Rates a board by how far the logical solver gets on its own and how much
+/// searching is left once it stalls.
+pub fn rate_difficulty(game_state: &GameState) -> DifficultyReport {
+    let solutions = count_solutions(game_state.clone(), Some(2));
+
+    let logical = solve_logically(game_state);
+    let solved_logically = logical.is_solved();
+    let techniques = logical.technique_counts();
+
+    let (guesses, backtracks, branching_profile) = if solved_logically {
+        (0, 0, Vec::new())
+    } else {
+        let options = SolveOptions {
+            symmetry_pruning: false,
+            ..SolveOptions::default()
+        };
+        let (_, stats) = depth_first_search_with_options(logical.state, &options);
+        // the stalled state itself is not a guess
+        (
+            stats.states_visited - 1,
+            stats.backtracks,
+            stats.branching_profile,
+        )
+    };
+
+    let score = techniques
+        .iter()
+        .map(|(technique, &count)| technique.weight() * count as f32)
+        .sum::<f32>()
+        + GUESS_WEIGHT * guesses as f32
+        + BACKTRACK_WEIGHT * backtracks as f32;
+
+    let difficulty = if !solved_logically {
+        Difficulty::Expert
+    } else {
+        match techniques.keys().next_back() {
+            Some(Technique::Contradiction) => Difficulty::Hard,
+            Some(Technique::RegionLineSets) => Difficulty::Medium,
+            _ => Difficulty::Easy,
+        }
+    };
+
+    DifficultyReport {
+        difficulty,
+        score,
+        solutions,
+        techniques,
+        solved_logically,
+        guesses,
+        backtracks,
+        branching_profile,
+    }
+}
+
+#[cfg(test)]
+mod test;
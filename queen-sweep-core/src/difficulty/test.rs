@@ -0,0 +1,37 @@
+use crate::fixtures::{puzzle_8x8_sol, puzzle_11x11_sol, row_regions};
+use crate::{Difficulty, GameState, Technique, rate_difficulty};
+
+#[test]
+fn test_rate_easy_board() {
+    let state = GameState::from_color_regions(puzzle_8x8_sol(), None).unwrap();
+    let report = rate_difficulty(&state);
+
+    assert!(report.is_unique());
+    assert!(report.solved_logically);
+    assert_eq!(report.difficulty, Difficulty::Easy);
+    assert_eq!(report.guesses, 0);
+    assert!(report.branching_profile.is_empty());
+}
+
+#[test]
+fn test_rate_harder_board_scores_higher() {
+    let easy = rate_difficulty(&GameState::from_color_regions(puzzle_8x8_sol(), None).unwrap());
+    let hard = rate_difficulty(&GameState::from_color_regions(puzzle_11x11_sol(), None).unwrap());
+
+    assert!(hard.is_unique());
+    assert!(hard.difficulty > easy.difficulty);
+    assert!(hard.score > easy.score);
+    assert!(hard.hardest_technique() >= Some(Technique::RegionLineSets));
+}
+
+#[test]
+fn test_rate_ambiguous_board() {
+    let state = GameState::from_color_regions(row_regions(6), None).unwrap();
+    let report = rate_difficulty(&state);
+
+    assert_eq!(report.solutions, 2);
+    assert!(!report.is_unique());
+    assert_eq!(report.difficulty, Difficulty::Expert);
+    assert!(report.guesses > 0);
+    assert!(!report.branching_profile.is_empty());
+}
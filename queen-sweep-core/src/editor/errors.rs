@@ -0,0 +1,52 @@
+use crate::GameStateError;
+
+#[derive(Debug)]
+pub enum EditError {
+    InvalidBoard(GameStateError),
+
+    OutOfBounds { r: usize, c: usize },
+
+    UnknownRegion { color: u8 },
+
+    NotAdjacent { r: usize, c: usize, color: u8 },
+
+    ForeignCell { r: usize, c: usize },
+
+    EmptyRegion { color: u8 },
+
+    DisconnectedRegion { color: u8 },
+}
+
+impl std::fmt::Display for EditError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidBoard(e) => {
+                write!(f, "Invalid board: {}", e)
+            }
+            Self::OutOfBounds { r, c } => {
+                write!(f, "Cell ({}, {}) is outside the board", r, c)
+            }
+            Self::UnknownRegion { color } => {
+                write!(f, "Region {} does not exist", color)
+            }
+            Self::NotAdjacent { r, c, color } => {
+                write!(f, "Cell ({}, {}) does not touch region {}", r, c, color)
+            }
+            Self::ForeignCell { r, c } => {
+                write!(f, "Cell ({}, {}) is not part of the edited regions", r, c)
+            }
+            Self::EmptyRegion { color } => {
+                write!(f, "Edit would leave region {} without cells", color)
+            }
+            Self::DisconnectedRegion { color } => {
+                write!(f, "Edit would split region {} into pieces", color)
+            }
+        }
+    }
+}
+
+impl From<GameStateError> for EditError {
+    fn from(e: GameStateError) -> Self {
+        Self::InvalidBoard(e)
+    }
+}
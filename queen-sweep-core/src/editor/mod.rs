@@ -0,0 +1,216 @@
+mod errors;
+pub use errors::EditError;
+
+use std::collections::VecDeque;
+
+use crate::{GameState, count_solutions, generator::neighbors, solve_logically};
+
+const DEFAULT_SOLUTION_LIMIT: usize = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditOutcome {
+    /// Solutions of the edited layout, capped at the editor's solution limit.
+    pub solutions: usize,
+    /// Cells that logical deduction now forces a queen into.
+    pub newly_forced: Vec<(usize, usize)>,
+    /// Cells that were forced before the edit and no longer are.
+    pub no_longer_forced: Vec<(usize, usize)>,
+}
+
+impl EditOutcome {
+    #[inline]
+    pub fn is_unique(&self) -> bool {
+        self.solutions == 1
+    }
+}
+
+/// Edits the region layout of a board. Every edit keeps each region connected
+/// and the number of regions unchanged, and is rejected otherwise.
+#[derive(Debug, Clone)]
+pub struct LayoutEditor {
+    rows: usize,
+    cols: usize,
+    region_count: usize,
+    colors: Vec<u8>,
+    forced: Vec<(usize, usize)>,
+    solution_limit: usize,
+}
+
+impl LayoutEditor {
+    pub fn new(color_regions: Vec<Vec<u8>>) -> Result<Self, EditError> {
+        let state = GameState::try_from(color_regions)?;
+
+        Ok(LayoutEditor {
+            rows: state.rows(),
+            cols: state.cols(),
+            region_count: state.region_count(),
+            colors: state
+                .colors()
+                .to_ids()
+                .expect("u8 layouts have fewer than 255 regions"),
+            forced: forced_cells(&state),
+            solution_limit: DEFAULT_SOLUTION_LIMIT,
+        })
+    }
+
+    /// Caps how many solutions an edit counts. The default of 2 is enough to
+    /// tell whether a layout is unique.
+    pub fn with_solution_limit(mut self, limit: usize) -> Self {
+        self.solution_limit = limit.max(1);
+        self
+    }
+
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[inline]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[inline]
+    pub fn color_at(&self, r: usize, c: usize) -> u8 {
+        self.colors[r * self.cols + c]
+    }
+
+    /// Cells forced by logical deduction on the current layout, sorted.
+    #[inline]
+    pub fn forced_cells(&self) -> &[(usize, usize)] {
+        &self.forced
+    }
+
+    pub fn regions(&self) -> Vec<Vec<u8>> {
+        self.colors
+            .chunks(self.cols)
+            .map(|row| row.to_vec())
+            .collect()
+    }
+
+    pub fn game_state(&self) -> GameState {
+        GameState::try_from(self.regions()).expect("edited layouts stay valid")
+    }
+
+    /// Moves cell `(r, c)` into the orthogonally adjacent region `color`.
+    pub fn move_cell(&mut self, r: usize, c: usize, color: u8) -> Result<EditOutcome, EditError> {
+        let idx = self.cell_idx(r, c)?;
+        self.check_region(color)?;
+
+        let from = self.colors[idx];
+        if from == color || !neighbors(self.rows, self.cols, idx).any(|n| self.colors[n] == color) {
+            return Err(EditError::NotAdjacent { r, c, color });
+        }
+
+        let mut colors = self.colors.clone();
+        colors[idx] = color;
+        self.apply(colors, &[from])
+    }
+
+    /// Merges regions `a` and `b`, then splits the union again by giving `cells`
+    /// to `a` and every other cell of the union to `b`.
+    pub fn merge_and_split(
+        &mut self,
+        a: u8,
+        b: u8,
+        cells: &[(usize, usize)],
+    ) -> Result<EditOutcome, EditError> {
+        self.check_region(a)?;
+        self.check_region(b)?;
+
+        let mut colors = self.colors.clone();
+        for color in colors.iter_mut().filter(|color| **color == a) {
+            *color = b;
+        }
+
+        for &(r, c) in cells {
+            let idx = self.cell_idx(r, c)?;
+            if colors[idx] != b {
+                return Err(EditError::ForeignCell { r, c });
+            }
+            colors[idx] = a;
+        }
+
+        self.apply(colors, &[a, b])
+    }
+
+    /// Commits `colors` if the `touched` regions are still non-empty and connected.
+    fn apply(&mut self, colors: Vec<u8>, touched: &[u8]) -> Result<EditOutcome, EditError> {
+        for &color in touched {
+            if !colors.contains(&color) {
+                return Err(EditError::EmptyRegion { color });
+            }
+            if !is_connected(self.rows, self.cols, &colors, color) {
+                return Err(EditError::DisconnectedRegion { color });
+            }
+        }
+
+        self.colors = colors;
+        let state = self.game_state();
+        let solutions = count_solutions(state.clone(), Some(self.solution_limit));
+        let forced = forced_cells(&state);
+
+        let outcome = EditOutcome {
+            solutions,
+            newly_forced: difference(&forced, &self.forced),
+            no_longer_forced: difference(&self.forced, &forced),
+        };
+        self.forced = forced;
+        Ok(outcome)
+    }
+
+    fn cell_idx(&self, r: usize, c: usize) -> Result<usize, EditError> {
+        if r >= self.rows || c >= self.cols {
+            return Err(EditError::OutOfBounds { r, c });
+        }
+        Ok(r * self.cols + c)
+    }
+
+    fn check_region(&self, color: u8) -> Result<(), EditError> {
+        if (color as usize) < self.region_count {
+            Ok(())
+        } else {
+            Err(EditError::UnknownRegion { color })
+        }
+    }
+}
+
+fn forced_cells(state: &GameState) -> Vec<(usize, usize)> {
+    let mut forced = solve_logically(state).forced_queens();
+    forced.sort_unstable();
+    forced
+}
+
+fn difference(cells: &[(usize, usize)], other: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    cells
+        .iter()
+        .filter(|cell| !other.contains(cell))
+        .copied()
+        .collect()
+}
+
+fn is_connected(rows: usize, cols: usize, colors: &[u8], color: u8) -> bool {
+    let Some(start) = colors.iter().position(|&c| c == color) else {
+        return false;
+    };
+
+    let mut visited = vec![false; colors.len()];
+    let mut queue = VecDeque::from([start]);
+    visited[start] = true;
+    let mut reached = 1;
+
+    while let Some(idx) = queue.pop_front() {
+        for n in neighbors(rows, cols, idx) {
+            if !visited[n] && colors[n] == color {
+                visited[n] = true;
+                reached += 1;
+                queue.push_back(n);
+            }
+        }
+    }
+
+    reached == colors.iter().filter(|&&c| c == color).count()
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,106 @@
+use crate::fixtures::row_regions;
+use crate::{
+    EditError, GameState, LayoutEditor, SeededRng, count_solutions, generate_board, solve_logically,
+};
+
+#[test]
+fn test_move_cell_keeps_regions_connected() {
+    let mut editor = LayoutEditor::new(row_regions(5)).unwrap();
+
+    assert!(matches!(
+        editor.move_cell(0, 2, 1),
+        Err(EditError::DisconnectedRegion { color: 0 })
+    ));
+    assert!(matches!(
+        editor.move_cell(0, 0, 2),
+        Err(EditError::NotAdjacent { .. })
+    ));
+    assert!(matches!(
+        editor.move_cell(5, 0, 1),
+        Err(EditError::OutOfBounds { .. })
+    ));
+    // rejected edits leave the layout untouched
+    assert_eq!(editor.regions(), row_regions(5));
+
+    editor.move_cell(0, 0, 1).unwrap();
+    assert_eq!(editor.color_at(0, 0), 1);
+}
+
+#[test]
+fn test_new_rejects_invalid_layouts() {
+    assert!(matches!(
+        LayoutEditor::new(vec![vec![0, 0, 1], vec![0, 1, 1]]),
+        Err(EditError::InvalidBoard(_))
+    ));
+
+    let mut editor = LayoutEditor::new(row_regions(4)).unwrap();
+    assert_eq!((editor.rows(), editor.cols()), (4, 4));
+    assert!(matches!(
+        editor.move_cell(0, 0, 4),
+        Err(EditError::UnknownRegion { color: 4 })
+    ));
+}
+
+#[test]
+fn test_merge_and_split_preserves_region_count() {
+    let mut editor = LayoutEditor::new(row_regions(4)).unwrap();
+
+    assert!(matches!(
+        editor.merge_and_split(0, 1, &[(0, 0), (1, 3)]),
+        Err(EditError::DisconnectedRegion { color: 0 })
+    ));
+    assert!(matches!(
+        editor.merge_and_split(0, 1, &[(2, 0)]),
+        Err(EditError::ForeignCell { r: 2, c: 0 })
+    ));
+
+    let outcome = editor
+        .merge_and_split(0, 1, &[(0, 0), (0, 1), (1, 0), (1, 1)])
+        .unwrap();
+    assert_eq!(editor.regions()[0], vec![0, 0, 1, 1]);
+    assert_eq!(editor.regions()[1], vec![0, 0, 1, 1]);
+    assert_eq!(editor.game_state().colors_with_queens().len(), 4);
+    assert_eq!(
+        outcome.solutions,
+        count_solutions(editor.game_state(), Some(2))
+    );
+}
+
+#[test]
+fn test_edit_outcome_tracks_solutions_and_forced_cells() {
+    let regions = generate_board(7, &mut SeededRng::new(3)).unwrap();
+    let mut editor = LayoutEditor::new(regions).unwrap().with_solution_limit(10);
+    let mut edits = 0;
+
+    for idx in 0..49 {
+        let (r, c) = (idx / 7, idx % 7);
+        let before = editor.forced_cells().to_vec();
+        let target = (editor.color_at(r, c) + 1) % 7;
+
+        let Ok(outcome) = editor.move_cell(r, c, target) else {
+            continue;
+        };
+        edits += 1;
+
+        let state = GameState::try_from(editor.regions()).unwrap();
+        assert_eq!(outcome.solutions, count_solutions(state.clone(), Some(10)));
+
+        let mut forced = solve_logically(&state).forced_queens();
+        forced.sort_unstable();
+        assert_eq!(editor.forced_cells(), forced);
+        assert!(
+            outcome
+                .newly_forced
+                .iter()
+                .all(|cell| forced.contains(cell) && !before.contains(cell))
+        );
+        assert!(
+            outcome
+                .no_longer_forced
+                .iter()
+                .all(|cell| before.contains(cell) && !forced.contains(cell))
+        );
+    }
+
+    assert!(edits > 0);
+}
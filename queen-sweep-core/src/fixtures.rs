@@ -0,0 +1,36 @@
+pub(crate) fn puzzle_8x8_sol() -> Vec<Vec<u8>> {
+    vec![
+        vec![0, 0, 1, 1, 1, 2, 2, 2],
+        vec![0, 3, 1, 3, 1, 4, 2, 2],
+        vec![0, 3, 1, 3, 1, 2, 2, 2],
+        vec![0, 3, 3, 3, 1, 5, 6, 2],
+        vec![0, 3, 3, 3, 1, 5, 6, 6],
+        vec![0, 3, 7, 3, 1, 5, 6, 6],
+        vec![7, 3, 7, 3, 1, 5, 5, 6],
+        vec![7, 7, 7, 7, 6, 6, 6, 6],
+    ]
+}
+
+pub(crate) fn puzzle_3x3_nosol() -> Vec<Vec<u8>> {
+    vec![vec![0, 0, 1], vec![1, 1, 2], vec![2, 2, 2]]
+}
+
+pub(crate) fn puzzle_11x11_sol() -> Vec<Vec<u8>> {
+    vec![
+        vec![0, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2],
+        vec![0, 3, 3, 1, 1, 1, 2, 2, 2, 2, 2],
+        vec![0, 3, 3, 1, 1, 2, 2, 2, 2, 2, 2],
+        vec![0, 0, 0, 0, 2, 2, 2, 4, 4, 4, 4],
+        vec![0, 5, 5, 6, 7, 7, 2, 2, 2, 4, 4],
+        vec![0, 5, 5, 6, 7, 7, 7, 7, 7, 7, 4],
+        vec![0, 5, 5, 6, 7, 7, 7, 7, 7, 7, 4],
+        vec![0, 5, 5, 6, 7, 7, 8, 9, 7, 7, 4],
+        vec![0, 5, 5, 6, 7, 7, 8, 9, 7, 7, 8],
+        vec![10, 5, 5, 6, 7, 7, 8, 9, 7, 7, 8],
+        vec![10, 10, 10, 6, 6, 8, 8, 8, 8, 8, 8],
+    ]
+}
+
+pub(crate) fn row_regions(size: usize) -> Vec<Vec<u8>> {
+    (0..size).map(|r| vec![r as u8; size]).collect()
+}
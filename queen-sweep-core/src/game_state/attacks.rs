@@ -0,0 +1,32 @@
+/// Cells next to every cell under the adjacency rule, stored back to back in
+/// one buffer. Diagonals are walked on demand like rows and columns, which
+/// keeps the table at a few entries per cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AttackTable {
+    // neighbors of cell `i` are `cells[offsets[i]..offsets[i + 1]]`
+    offsets: Box<[usize]>,
+    cells: Box<[usize]>,
+}
+
+impl AttackTable {
+    pub fn new(num_cells: usize, neighbors: impl Fn(usize) -> Vec<usize>) -> Self {
+        let mut offsets = Vec::with_capacity(num_cells + 1);
+        let mut cells = Vec::new();
+
+        offsets.push(0);
+        for idx in 0..num_cells {
+            cells.extend(neighbors(idx));
+            offsets.push(cells.len());
+        }
+
+        AttackTable {
+            offsets: offsets.into_boxed_slice(),
+            cells: cells.into_boxed_slice(),
+        }
+    }
+
+    #[inline]
+    pub fn neighbors(&self, idx: usize) -> &[usize] {
+        &self.cells[self.offsets[idx]..self.offsets[idx + 1]]
+    }
+}
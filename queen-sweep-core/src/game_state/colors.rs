@@ -0,0 +1,115 @@
+use std::{fmt::Debug, hash::Hash, rc::Rc};
+
+/// Integer types usable as region identifiers in a color grid.
+pub trait RegionId: Copy + Eq + Ord + Hash + Debug {
+    /// Marks cells that are not part of the board.
+    const VOID: Self;
+    /// Largest number of rows or columns accepted with this id type.
+    const MAX_BOARD_SIZE: usize;
+
+    fn from_index(index: usize) -> Self;
+    fn index(self) -> usize;
+}
+
+impl RegionId for u8 {
+    const VOID: Self = u8::MAX;
+    const MAX_BOARD_SIZE: usize = u8::MAX as usize;
+
+    #[inline]
+    fn from_index(index: usize) -> Self {
+        index as u8
+    }
+
+    #[inline]
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl RegionId for u16 {
+    const VOID: Self = u16::MAX;
+    const MAX_BOARD_SIZE: usize = u16::MAX as usize;
+
+    #[inline]
+    fn from_index(index: usize) -> Self {
+        index as u16
+    }
+
+    #[inline]
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Region of every cell. Boards with up to 255 regions keep the compact `u8`
+/// form, larger ones switch to `u16`.
+#[derive(Debug, Clone)]
+pub enum ColorGrid {
+    Narrow(Rc<[u8]>),
+    Wide(Rc<[u16]>),
+}
+
+impl ColorGrid {
+    /// Builds the grid from normalized region indices, `None` marking void cells.
+    pub(crate) fn new(regions: &[Option<usize>], region_count: usize) -> Self {
+        fn ids<T: RegionId>(regions: &[Option<usize>]) -> Rc<[T]> {
+            regions
+                .iter()
+                .map(|region| region.map_or(T::VOID, T::from_index))
+                .collect()
+        }
+
+        if region_count <= u8::MAX as usize {
+            ColorGrid::Narrow(ids(regions))
+        } else {
+            ColorGrid::Wide(ids(regions))
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self {
+            ColorGrid::Narrow(ids) => ids.len(),
+            ColorGrid::Wide(ids) => ids.len(),
+        }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Region of cell `idx`, or `None` for void cells.
+    #[inline]
+    pub fn region(&self, idx: usize) -> Option<usize> {
+        match self {
+            ColorGrid::Narrow(ids) => Self::lookup(ids[idx]),
+            ColorGrid::Wide(ids) => Self::lookup(ids[idx]),
+        }
+    }
+
+    #[inline]
+    pub fn is_void(&self, idx: usize) -> bool {
+        self.region(idx).is_none()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Option<usize>> + '_ {
+        (0..self.len()).map(|idx| self.region(idx))
+    }
+
+    /// Region ids of every cell as `T`, or `None` if some region does not fit.
+    pub fn to_ids<T: RegionId>(&self) -> Option<Vec<T>> {
+        self.iter()
+            .map(|region| match region {
+                Some(region) if region < T::VOID.index() => Some(T::from_index(region)),
+                Some(_) => None,
+                None => Some(T::VOID),
+            })
+            .collect()
+    }
+
+    #[inline]
+    fn lookup<T: RegionId>(id: T) -> Option<usize> {
+        (id != T::VOID).then(|| id.index())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{CellState, GameState};
+use crate::{CellState, GameState, game_state::ColorGrid};
 use owo_colors::OwoColorize;
 
 use owo_colors::AnsiColors as oac;
@@ -26,23 +26,21 @@ impl GameState {
     }
 
     fn print_header(&self) {
-        let size = self.size;
         print!("   ");
-        for i in 0..size {
+        for i in 0..self.cols() {
             print!(" {} ", i.dimmed());
         }
         println!();
     }
 
     fn print_rows(&self) {
-        let size = self.size;
         let states = self.states();
         let colors = self.colors();
 
-        for r in 0..size {
+        for r in 0..self.rows() {
             print!("{:2} ", r.dimmed());
-            let row_offset = r * size;
-            for c in 0..size {
+            let row_offset = r * self.cols();
+            for c in 0..self.cols() {
                 let idx = row_offset + c;
                 self.print_cell(idx, states, colors);
             }
@@ -50,8 +48,13 @@ impl GameState {
         }
     }
 
-    fn print_cell(&self, idx: usize, states: &[CellState], colors: &[u8]) {
-        let color = COLOR_TABLE[(colors[idx] as usize) % COLOR_TABLE.len()];
+    fn print_cell(&self, idx: usize, states: &[CellState], colors: &ColorGrid) {
+        let Some(region) = colors.region(idx) else {
+            print!("   ");
+            return;
+        };
+
+        let color = COLOR_TABLE[region % COLOR_TABLE.len()];
 
         match states[idx] {
             CellState::Queen => {
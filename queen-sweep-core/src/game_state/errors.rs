@@ -2,11 +2,49 @@
 pub enum GameStateError {
     InexistentBoard,
 
-    NonSquareBoard { rows: usize, cols: usize },
+    RaggedBoard {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
 
-    InvalidCellCount { expected: usize, found: usize },
+    InvalidCellCount {
+        expected: usize,
+        found: usize,
+    },
 
-    BoardTooLarge { size: usize, max_size: usize },
+    BoardTooLarge {
+        size: usize,
+        max_size: usize,
+    },
+
+    InvalidQueensPerUnit {
+        queens_per_unit: usize,
+        size: usize,
+    },
+
+    RowOverfull {
+        row: usize,
+        queens: usize,
+        len: usize,
+    },
+
+    ColumnOverfull {
+        col: usize,
+        queens: usize,
+        len: usize,
+    },
+
+    QueenCountLength {
+        expected: usize,
+        found: usize,
+    },
+
+    QueenCountMismatch {
+        rows: usize,
+        cols: usize,
+        regions: usize,
+    },
 }
 
 impl std::fmt::Display for GameStateError {
@@ -15,8 +53,12 @@ impl std::fmt::Display for GameStateError {
             Self::InexistentBoard => {
                 write!(f, "Board size must be greater than 0")
             }
-            Self::NonSquareBoard { rows, cols } => {
-                write!(f, "Board is not square: {} rows x {} columns", rows, cols)
+            Self::RaggedBoard {
+                row,
+                expected,
+                found,
+            } => {
+                write!(f, "Row {} has {} cells, expected {}", row, found, expected)
             }
             Self::InvalidCellCount { expected, found } => {
                 write!(
@@ -32,6 +74,48 @@ impl std::fmt::Display for GameStateError {
                     size, max_size
                 )
             }
+            Self::InvalidQueensPerUnit {
+                queens_per_unit,
+                size,
+            } => {
+                write!(
+                    f,
+                    "{} queens per unit is not a valid target for a board with {} rows",
+                    queens_per_unit, size
+                )
+            }
+            Self::RowOverfull { row, queens, len } => {
+                write!(
+                    f,
+                    "Row {} needs {} queens, which do not fit in {} cells under the adjacency rule",
+                    row, queens, len
+                )
+            }
+            Self::ColumnOverfull { col, queens, len } => {
+                write!(
+                    f,
+                    "Column {} needs {} queens, which do not fit in {} cells under the adjacency rule",
+                    col, queens, len
+                )
+            }
+            Self::QueenCountLength { expected, found } => {
+                write!(
+                    f,
+                    "Expected {} per-line queen counts, found {}",
+                    expected, found
+                )
+            }
+            Self::QueenCountMismatch {
+                rows,
+                cols,
+                regions,
+            } => {
+                write!(
+                    f,
+                    "Queen totals disagree: {} over rows, {} over columns, {} over regions",
+                    rows, cols, regions
+                )
+            }
         }
     }
 }
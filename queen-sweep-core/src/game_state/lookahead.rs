@@ -0,0 +1,226 @@
+use crate::{CellState, GameState};
+
+/// How thoroughly `place_queen` checks the empty cells left after a placement.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LookaheadDepth {
+    /// Only block cells ruled out directly by the placed queen.
+    Disabled,
+    /// Block cells whose queen would leave another region without room for its queens.
+    #[default]
+    Regions,
+    /// Also block cells whose queen would confine more regions to a row or
+    /// column than that line has queens left.
+    Lines,
+}
+
+/// Unit counts behind the placement lookahead, kept in sync while
+/// `place_queen` blocks the cells that fail it.
+pub(crate) struct Lookahead {
+    row_queens: Vec<usize>,
+    col_queens: Vec<usize>,
+    region_queens: Vec<usize>,
+    // queens plus empty cells of every region
+    region_supply: Vec<usize>,
+    region_open: Vec<bool>,
+    // open regions that can no longer get all their queens
+    short_regions: usize,
+
+    // scratch space for a single check
+    marked: Vec<bool>,
+    marked_cells: Vec<usize>,
+    lost: Vec<usize>,
+    touched: Vec<usize>,
+    // queens owed to every row and column by the regions confined to it
+    row_demand: Vec<usize>,
+    col_demand: Vec<usize>,
+}
+
+impl GameState {
+    pub(crate) fn lookahead(&self, states: &[CellState], colors_with_queens: &[bool]) -> Lookahead {
+        let regions = self.region_count();
+        let mut lookahead = Lookahead {
+            row_queens: vec![0; self.rows],
+            col_queens: vec![0; self.cols],
+            region_queens: vec![0; regions],
+            region_supply: vec![0; regions],
+            region_open: colors_with_queens.iter().map(|&full| !full).collect(),
+            short_regions: 0,
+            marked: vec![false; states.len()],
+            marked_cells: Vec::new(),
+            lost: vec![0; regions],
+            touched: Vec::new(),
+            row_demand: vec![0; self.rows],
+            col_demand: vec![0; self.cols],
+        };
+
+        for (idx, &state) in states.iter().enumerate() {
+            let (r, c) = self.idx_to_pos(idx);
+            match state {
+                CellState::Queen => {
+                    let color = self.color_at_idx(idx);
+                    lookahead.row_queens[r] += 1;
+                    lookahead.col_queens[c] += 1;
+                    lookahead.region_queens[color] += 1;
+                    lookahead.region_supply[color] += 1;
+                }
+                CellState::Empty => lookahead.region_supply[self.color_at_idx(idx)] += 1,
+                CellState::Blocked => {}
+            }
+        }
+
+        lookahead.short_regions = (0..regions)
+            .filter(|&color| lookahead.is_short(color, self.rules.queens_per_unit))
+            .count();
+        lookahead
+    }
+
+    /// Whether a queen fits on the empty cell `idx` without leaving another
+    /// region short of room for its queens, and with `Lines` also without
+    /// overfilling a row or column.
+    pub(crate) fn passes_lookahead(
+        &self,
+        lookahead: &mut Lookahead,
+        states: &[CellState],
+        idx: usize,
+        depth: LookaheadDepth,
+    ) -> bool {
+        if states[idx] != CellState::Empty {
+            return false;
+        }
+
+        let queens_per_unit = self.rules.queens_per_unit;
+        let queen_color = self.color_at_idx(idx);
+        if !lookahead.region_open[queen_color] {
+            return false;
+        }
+
+        // a short region other than the queen's own stays short
+        let own_short = lookahead.is_short(queen_color, queens_per_unit) as usize;
+        if lookahead.short_regions > own_short {
+            return false;
+        }
+
+        // cells the queen blocks, filling its own region only affects that region
+        let (r, c) = self.idx_to_pos(idx);
+        lookahead.mark(idx);
+        if lookahead.row_queens[r] + 1 == self.rules.row_target(r) {
+            (0..self.cols).for_each(|i| lookahead.mark(self.pos_to_idx(r, i)));
+        }
+        if lookahead.col_queens[c] + 1 == self.rules.col_target(c) {
+            (0..self.rows).for_each(|i| lookahead.mark(self.pos_to_idx(i, c)));
+        }
+        self.attacked_cells(idx)
+            .for_each(|attacked_idx| lookahead.mark(attacked_idx));
+
+        // all other regions must still have room for the queens they are missing
+        for i in 0..lookahead.marked_cells.len() {
+            let marked_idx = lookahead.marked_cells[i];
+            if states[marked_idx] != CellState::Empty {
+                continue;
+            }
+            let color = self.color_at_idx(marked_idx);
+            if lookahead.lost[color] == 0 {
+                lookahead.touched.push(color);
+            }
+            lookahead.lost[color] += 1;
+        }
+        let mut fits = !lookahead.touched.iter().any(|&color| {
+            color != queen_color
+                && lookahead.region_open[color]
+                && lookahead.region_supply[color] - lookahead.lost[color] < queens_per_unit
+        });
+        if fits && depth == LookaheadDepth::Lines {
+            fits = self.lines_have_room(lookahead, states, idx);
+        }
+
+        lookahead.clear();
+        fits
+    }
+
+    /// Whether every row and column still has room for the queens of the open
+    /// regions confined to it once a queen is placed on `idx`.
+    /// Expects the cells that queen blocks to be marked in `lookahead`.
+    fn lines_have_room(&self, lookahead: &mut Lookahead, states: &[CellState], idx: usize) -> bool {
+        let (queen_r, queen_c) = self.idx_to_pos(idx);
+        let queen_color = self.color_at_idx(idx);
+
+        for color in (0..self.region_count()).filter(|&color| lookahead.region_open[color]) {
+            let placed = lookahead.region_queens[color] + (color == queen_color) as usize;
+            let missing = self.rules.queens_per_unit.saturating_sub(placed);
+            if missing == 0 {
+                continue;
+            }
+
+            // the one row and column holding every remaining cell, if any
+            let (mut row, mut col) = (None, None);
+            let (mut same_row, mut same_col) = (true, true);
+            for &cell in self.region_index.cells(color) {
+                if states[cell] != CellState::Empty || lookahead.marked[cell] {
+                    continue;
+                }
+                let (r, c) = self.idx_to_pos(cell);
+                same_row &= *row.get_or_insert(r) == r;
+                same_col &= *col.get_or_insert(c) == c;
+                if !same_row && !same_col {
+                    break;
+                }
+            }
+
+            if same_row && let Some(r) = row {
+                lookahead.row_demand[r] += missing;
+            }
+            if same_col && let Some(c) = col {
+                lookahead.col_demand[c] += missing;
+            }
+        }
+
+        let row_room = |r: usize| {
+            let placed = lookahead.row_queens[r] + (r == queen_r) as usize;
+            self.rules.row_target(r).saturating_sub(placed)
+        };
+        let col_room = |c: usize| {
+            let placed = lookahead.col_queens[c] + (c == queen_c) as usize;
+            self.rules.col_target(c).saturating_sub(placed)
+        };
+
+        let fits = (0..self.rows).all(|r| lookahead.row_demand[r] <= row_room(r))
+            && (0..self.cols).all(|c| lookahead.col_demand[c] <= col_room(c));
+
+        lookahead.row_demand.fill(0);
+        lookahead.col_demand.fill(0);
+        fits
+    }
+}
+
+impl Lookahead {
+    /// Records that an empty cell of `color` got blocked.
+    pub(crate) fn block(&mut self, color: usize, queens_per_unit: usize) {
+        let was_short = self.is_short(color, queens_per_unit);
+        self.region_supply[color] -= 1;
+        if !was_short && self.is_short(color, queens_per_unit) {
+            self.short_regions += 1;
+        }
+    }
+
+    #[inline]
+    fn is_short(&self, color: usize, queens_per_unit: usize) -> bool {
+        self.region_open[color] && self.region_supply[color] < queens_per_unit
+    }
+
+    #[inline]
+    fn mark(&mut self, idx: usize) {
+        if !self.marked[idx] {
+            self.marked[idx] = true;
+            self.marked_cells.push(idx);
+        }
+    }
+
+    fn clear(&mut self) {
+        for idx in self.marked_cells.drain(..) {
+            self.marked[idx] = false;
+        }
+        for color in self.touched.drain(..) {
+            self.lost[color] = 0;
+        }
+    }
+}
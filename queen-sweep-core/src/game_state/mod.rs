@@ -1,6 +1,18 @@
+mod attacks;
+use attacks::AttackTable;
+
+mod colors;
+pub use colors::{ColorGrid, RegionId};
+
 mod errors;
 pub use errors::GameStateError;
 
+mod lookahead;
+pub use lookahead::LookaheadDepth;
+
+mod regions;
+pub use regions::RegionIndex;
+
 use std::{
     collections::{HashMap, HashSet, hash_map::DefaultHasher},
     hash::{Hash, Hasher},
@@ -8,33 +20,32 @@ use std::{
 };
 
 use crate::{
-    CellState,
-    heuristic::{HeuristicContext, HeuristicFn},
+    CellState, RuleSet, SymmetryGroup,
+    heuristic::{Heuristic, HeuristicContext, HeuristicFn, TieBreak, sort_placements},
 };
 
-const NEIGHBOR_DISPLACEMENTS: [(i32, i32); 8] = [
-    (-1, -1),
-    (-1, 0),
-    (-1, 1),
-    (0, -1),
-    (0, 1),
-    (1, -1),
-    (1, 0),
-    (1, 1),
-];
+const DIAGONAL_DIRECTIONS: [(i32, i32); 4] = [(-1, -1), (-1, 1), (1, -1), (1, 1)];
 pub const MAX_BOARD_SIZE: usize = u8::MAX as usize;
+/// Color of void cells when `RuleSet::void_cells` is set. `u16` layouts use `u16::MAX`.
+pub const VOID_COLOR: u8 = u8::MAX;
 
 #[derive(Debug, Clone)]
 pub struct GameState {
-    size: usize,
+    rows: usize,
+    cols: usize,
     states: Vec<CellState>,
+    // regions holding all the queens they need
     colors_with_queens: Vec<bool>,
 
     // immutable once initialized
-    colors: Rc<[u8]>,
-    color_masks: Rc<[bool]>,
+    colors: ColorGrid,
+    region_index: Rc<RegionIndex>,
+    attacks: Rc<AttackTable>,
+    rules: Rc<RuleSet>,
 
-    heuristic: Option<HeuristicFn>,
+    heuristic: Option<Rc<dyn Heuristic>>,
+    tie_break: TieBreak,
+    lookahead: LookaheadDepth,
 
     hash: u64,
 }
@@ -42,8 +53,18 @@ pub struct GameState {
 // Accessors
 impl GameState {
     #[inline]
-    pub fn size(&self) -> usize {
-        self.size
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[inline]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[inline]
+    pub fn region_count(&self) -> usize {
+        self.colors_with_queens.len()
     }
 
     #[inline]
@@ -57,107 +78,338 @@ impl GameState {
     }
 
     #[inline]
-    pub fn colors(&self) -> &[u8] {
+    pub fn colors(&self) -> &ColorGrid {
         &self.colors
     }
 
+    /// Cells of every region.
+    #[inline]
+    pub fn region_index(&self) -> &RegionIndex {
+        &self.region_index
+    }
+
     #[inline]
     pub fn hash(&self) -> u64 {
         self.hash
     }
+
+    #[inline]
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    #[inline]
+    pub fn heuristic(&self) -> Option<&dyn Heuristic> {
+        self.heuristic.as_deref()
+    }
+
+    #[inline]
+    pub fn tie_break(&self) -> &TieBreak {
+        &self.tie_break
+    }
+
+    #[inline]
+    pub fn lookahead_depth(&self) -> LookaheadDepth {
+        self.lookahead
+    }
+
+    /// Rotations and reflections that map the board onto itself. Only square
+    /// boards without per-line queen counts can have any beyond the identity.
+    #[inline]
+    pub fn symmetry_group(&self) -> SymmetryGroup {
+        if self.rows != self.cols
+            || self.rules.row_queens.is_some()
+            || self.rules.col_queens.is_some()
+        {
+            return SymmetryGroup::trivial();
+        }
+        SymmetryGroup::detect(self.rows, &self.colors)
+    }
+
+    /// Whether the board is square and needs one queen in every row, column and region.
+    pub(crate) fn is_classic(&self) -> bool {
+        self.rows == self.cols && self.region_count() == self.rows && self.rules.is_single_queen()
+    }
 }
 
 // Helper functions
 impl GameState {
     #[inline]
     fn pos_to_idx(&self, r: usize, c: usize) -> usize {
-        r * self.size + c
+        r * self.cols + c
     }
 
     #[inline]
     fn idx_to_pos(&self, idx: usize) -> (usize, usize) {
-        (idx / self.size, idx % self.size)
+        (idx / self.cols, idx % self.cols)
     }
 
     #[inline]
     pub fn is_goal_state(&self) -> bool {
-        self.colors_with_queens.iter().filter(|&&b| b).count() == self.size
+        let required: usize = (0..self.rows).map(|r| self.rules.row_target(r)).sum();
+        let filled = self.colors_with_queens.iter().filter(|&&b| b).count();
+        filled * self.rules.queens_per_unit == required
     }
 
     #[inline]
-    fn color_at_idx(&self, idx: usize) -> u8 {
-        self.colors[idx] as u8
+    fn color_at_idx(&self, idx: usize) -> usize {
+        self.colors
+            .region(idx)
+            .expect("void cells are never empty or queens")
+    }
+
+    /// Index of `(r, c)`, wrapping around the edges on toroidal boards.
+    /// `None` if the position lies off a regular board.
+    #[inline]
+    fn wrapped_idx(&self, r: i32, c: i32) -> Option<usize> {
+        let (rows, cols) = (self.rows as i32, self.cols as i32);
+
+        if self.rules.toroidal {
+            Some(self.pos_to_idx(r.rem_euclid(rows) as usize, c.rem_euclid(cols) as usize))
+        } else if r >= 0 && c >= 0 && r < rows && c < cols {
+            Some(self.pos_to_idx(r as usize, c as usize))
+        } else {
+            None
+        }
+    }
+
+    /// Cells next to `idx` under the adjacency rule, used to build the attack table.
+    fn adjacent_cells(&self, idx: usize) -> Vec<usize> {
+        let (r, c) = self.idx_to_pos(idx);
+        let mut cells: Vec<usize> = self
+            .rules
+            .adjacency
+            .displacements()
+            .iter()
+            .filter_map(|&(dr, dc)| self.wrapped_idx(r as i32 + dr, c as i32 + dc))
+            .collect();
+
+        // tiny toroidal boards can wrap a neighbor back onto the queen
+        cells.retain(|&neighbor_idx| neighbor_idx != idx);
+        cells.sort_unstable();
+        cells.dedup();
+        cells
     }
 
+    /// Cells next to `idx` under the adjacency rule.
     #[inline]
-    fn get_color_mask(&self, color: u8) -> &[bool] {
-        let num_cells = self.size * self.size;
-        let start = color as usize * num_cells;
-        let end = start + num_cells;
-        &self.color_masks[start..end]
+    pub(crate) fn neighbors(&self, idx: usize) -> &[usize] {
+        self.attacks.neighbors(idx)
+    }
+
+    /// Cells a queen on `idx` rules out through the adjacency and diagonal rules.
+    /// Diagonal cells that are also neighbors appear twice.
+    pub(crate) fn attacked_cells(&self, idx: usize) -> impl Iterator<Item = usize> + '_ {
+        let (r, c) = self.idx_to_pos(idx);
+        let (r, c) = (r as i32, c as i32);
+        let diagonals = self.rules.forbid_diagonals as usize * DIAGONAL_DIRECTIONS.len();
+
+        // on a torus the diagonal ends where it wraps back to the queen
+        let diagonal_cells = DIAGONAL_DIRECTIONS[..diagonals]
+            .iter()
+            .flat_map(move |&(dr, dc)| {
+                (1..).map_while(move |step| {
+                    self.wrapped_idx(r + dr * step, c + dc * step)
+                        .filter(|&diagonal_idx| diagonal_idx != idx)
+                })
+            });
+
+        self.attacks
+            .neighbors(idx)
+            .iter()
+            .copied()
+            .chain(diagonal_cells)
     }
 
     #[inline]
-    fn in_bounds(&self, r: i32, c: i32) -> bool {
-        r >= 0 && c >= 0 && r < self.size as i32 && c < self.size as i32
+    fn queens_in_row(&self, states: &[CellState], r: usize) -> usize {
+        (0..self.cols)
+            .filter(|&c| states[self.pos_to_idx(r, c)] == CellState::Queen)
+            .count()
+    }
+
+    #[inline]
+    fn queens_in_col(&self, states: &[CellState], c: usize) -> usize {
+        (0..self.rows)
+            .filter(|&r| states[self.pos_to_idx(r, c)] == CellState::Queen)
+            .count()
+    }
+
+    #[inline]
+    fn queens_in_region(&self, states: &[CellState], color: usize) -> usize {
+        self.region_index
+            .cells(color)
+            .iter()
+            .filter(|&&idx| states[idx] == CellState::Queen)
+            .count()
     }
 }
 
 impl GameState {
-    pub fn from_color_regions(
-        color_regions: Vec<Vec<u8>>,
+    pub fn from_color_regions<T: RegionId>(
+        color_regions: Vec<Vec<T>>,
+        heuristic: Option<HeuristicFn>,
+    ) -> Result<Self, GameStateError> {
+        GameState::from_color_regions_with_rules(color_regions, heuristic, RuleSet::default())
+    }
+
+    pub fn from_color_regions_with_rules<T: RegionId>(
+        color_regions: Vec<Vec<T>>,
         heuristic: Option<HeuristicFn>,
+        rules: RuleSet,
     ) -> Result<Self, GameStateError> {
-        let mut base = GameState::try_from(color_regions)?;
-        base.heuristic = heuristic;
+        let mut base = GameState::from_region_ids(color_regions, rules)?;
+        base.heuristic = heuristic.map(|f| Rc::new(f) as Rc<dyn Heuristic>);
         Ok(base)
     }
 
+    fn validate_rules(&self, rules: &RuleSet) -> Result<(), GameStateError> {
+        for (counts, lines) in [
+            (&rules.row_queens, self.rows),
+            (&rules.col_queens, self.cols),
+        ] {
+            if let Some(counts) = counts
+                && counts.len() != lines
+            {
+                return Err(GameStateError::QueenCountLength {
+                    expected: lines,
+                    found: counts.len(),
+                });
+            }
+        }
+
+        let row_targets: Vec<usize> = (0..self.rows).map(|r| rules.row_target(r)).collect();
+        let col_targets: Vec<usize> = (0..self.cols).map(|c| rules.col_target(c)).collect();
+
+        if rules.queens_per_unit == 0 {
+            return Err(GameStateError::InvalidQueensPerUnit {
+                queens_per_unit: 0,
+                size: self.rows,
+            });
+        }
+
+        // every line must have room for its queens under the adjacency rule
+        let too_many = |queens: usize, len: usize| queens > 0 && rules.min_line_len(queens) > len;
+        if let Some((row, &queens)) = row_targets
+            .iter()
+            .enumerate()
+            .find(|&(_, &queens)| too_many(queens, self.cols))
+        {
+            return Err(GameStateError::RowOverfull {
+                row,
+                queens,
+                len: self.cols,
+            });
+        }
+        if let Some((col, &queens)) = col_targets
+            .iter()
+            .enumerate()
+            .find(|&(_, &queens)| too_many(queens, self.rows))
+        {
+            return Err(GameStateError::ColumnOverfull {
+                col,
+                queens,
+                len: self.rows,
+            });
+        }
+
+        let rows: usize = row_targets.iter().sum();
+        let cols: usize = col_targets.iter().sum();
+        let regions = self.region_count() * rules.queens_per_unit;
+        if rows != cols || rows != regions {
+            return Err(GameStateError::QueenCountMismatch {
+                rows,
+                cols,
+                regions,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Orders the placements of this state and every state derived from it by `heuristic`.
+    pub fn with_heuristic(mut self, heuristic: impl Heuristic + 'static) -> Self {
+        self.heuristic = Some(Rc::new(heuristic));
+        self
+    }
+
+    /// Orders placements with equal heuristic scores, see `valid_placements`.
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Sets how thoroughly `place_queen` checks the remaining empty cells.
+    /// Applies to this state and every state derived from it.
+    pub fn with_lookahead(mut self, depth: LookaheadDepth) -> Self {
+        self.lookahead = depth;
+        self
+    }
+
     pub fn place_queen(&self, r: usize, c: usize) -> Self {
+        self.place_queen_with_lookahead(r, c, self.lookahead)
+    }
+
+    /// Places a queen without blocking cells that fail the lookahead check,
+    /// leaving those deductions to the caller.
+    pub(crate) fn place_queen_without_lookahead(&self, r: usize, c: usize) -> Self {
+        self.place_queen_with_lookahead(r, c, LookaheadDepth::Disabled)
+    }
+
+    fn place_queen_with_lookahead(&self, r: usize, c: usize, depth: LookaheadDepth) -> Self {
         let mut new_states = self.states.clone();
         let mut new_colors_with_queens = self.colors_with_queens.clone();
 
         let idx = self.pos_to_idx(r, c);
 
-        // block row and col
-        for i in 0..self.size {
-            let row_idx = self.pos_to_idx(r, i);
-            let col_idx = self.pos_to_idx(i, c);
+        // place queen
+        new_states[idx] = CellState::Queen;
 
-            new_states[row_idx] = CellState::Blocked;
-            new_states[col_idx] = CellState::Blocked;
+        // block row and col once they are full
+        if self.queens_in_row(&new_states, r) == self.rules.row_target(r) {
+            for i in 0..self.cols {
+                let row_idx = self.pos_to_idx(r, i);
+                if new_states[row_idx] == CellState::Empty {
+                    new_states[row_idx] = CellState::Blocked;
+                }
+            }
+        }
+        if self.queens_in_col(&new_states, c) == self.rules.col_target(c) {
+            for i in 0..self.rows {
+                let col_idx = self.pos_to_idx(i, c);
+                if new_states[col_idx] == CellState::Empty {
+                    new_states[col_idx] = CellState::Blocked;
+                }
+            }
         }
 
-        // block neighbors
-        for (dr, dc) in NEIGHBOR_DISPLACEMENTS {
-            let nr = r as i32 + dr;
-            let nc = c as i32 + dc;
-
-            if nr >= 0 && nr < self.size as i32 && nc >= 0 && nc < self.size as i32 {
-                let neighbor_idx = self.pos_to_idx(nr as usize, nc as usize);
-                new_states[neighbor_idx] = CellState::Blocked;
+        // block neighbors and diagonals
+        for attacked_idx in self.attacked_cells(idx) {
+            if new_states[attacked_idx] == CellState::Empty {
+                new_states[attacked_idx] = CellState::Blocked;
             }
         }
 
-        // block color region
+        // block color region once it is full
         let color = self.color_at_idx(idx);
-        let color_mask = self.get_color_mask(color);
-        for i in 0..new_states.len() {
-            if color_mask[i] {
-                new_states[i] = CellState::Blocked;
+        if self.queens_in_region(&new_states, color) == self.rules.queens_per_unit {
+            for &i in self.region_index.cells(color) {
+                if new_states[i] == CellState::Empty {
+                    new_states[i] = CellState::Blocked;
+                }
             }
+            new_colors_with_queens[color] = true;
         }
 
-        // place queen
-        new_states[idx] = CellState::Queen;
-        new_colors_with_queens[color as usize] = true;
-
         // block all invalid moves
-        for idx in 0..new_states.len() {
-            if new_states[idx] == CellState::Empty {
-                let (r, c) = self.idx_to_pos(idx);
-                if !self.can_place_queen(&new_states, &new_colors_with_queens, r, c) {
+        if depth != LookaheadDepth::Disabled {
+            let mut lookahead = self.lookahead(&new_states, &new_colors_with_queens);
+            for idx in 0..new_states.len() {
+                if new_states[idx] == CellState::Empty
+                    && !self.passes_lookahead(&mut lookahead, &new_states, idx, depth)
+                {
                     new_states[idx] = CellState::Blocked;
+                    lookahead.block(self.color_at_idx(idx), self.rules.queens_per_unit);
                 }
             }
         }
@@ -165,16 +417,52 @@ impl GameState {
         let hash = compute_hash(&new_states);
 
         GameState {
-            size: self.size,
+            rows: self.rows,
+            cols: self.cols,
             states: new_states,
             colors_with_queens: new_colors_with_queens,
-            colors: Rc::clone(&self.colors),
-            color_masks: Rc::clone(&self.color_masks),
-            heuristic: self.heuristic,
+            colors: self.colors.clone(),
+            region_index: Rc::clone(&self.region_index),
+            attacks: Rc::clone(&self.attacks),
+            rules: Rc::clone(&self.rules),
+            heuristic: self.heuristic.clone(),
+            tie_break: self.tie_break.clone(),
+            lookahead: self.lookahead,
+            hash,
+        }
+    }
+
+    /// Marks the given empty cells as blocked. Queens are left untouched.
+    pub fn block_cells(&self, cells: &[(usize, usize)]) -> Self {
+        let mut new_states = self.states.clone();
+
+        for &(r, c) in cells {
+            let idx = self.pos_to_idx(r, c);
+            if new_states[idx] == CellState::Empty {
+                new_states[idx] = CellState::Blocked;
+            }
+        }
+
+        let hash = compute_hash(&new_states);
+
+        GameState {
+            rows: self.rows,
+            cols: self.cols,
+            states: new_states,
+            colors_with_queens: self.colors_with_queens.clone(),
+            colors: self.colors.clone(),
+            region_index: Rc::clone(&self.region_index),
+            attacks: Rc::clone(&self.attacks),
+            rules: Rc::clone(&self.rules),
+            heuristic: self.heuristic.clone(),
+            tie_break: self.tie_break.clone(),
+            lookahead: self.lookahead,
             hash,
         }
     }
 
+    /// Empty cells ordered by the heuristic, lowest score first. Equal scores
+    /// are ordered by the tie-break policy and NaN scores come last.
     pub fn valid_placements(&self) -> Vec<(usize, usize)> {
         let positions: Vec<(usize, usize)> = self
             .states
@@ -189,25 +477,111 @@ impl GameState {
             })
             .collect();
 
-        let heuristic_fn = match self.heuristic {
-            Some(f) => f,
-            None => return positions,
-        };
+        self.order_placements(positions)
+    }
+
+    /// Empty cells of the most constrained unit, ordered like `valid_placements`.
+    /// Every solution has a queen among them, so branching over them alone keeps
+    /// the search complete. With one queen per unit no two branches can lead to
+    /// the same queen set.
+    pub fn unit_placements(&self) -> Vec<(usize, usize)> {
+        self.order_placements(self.most_constrained_unit())
+    }
+
+    /// Sorts `positions` by the heuristic, ordering ties by the tie-break.
+    fn order_placements(&self, positions: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+        if self.heuristic.is_none() && matches!(self.tie_break, TieBreak::RowMajor) {
+            return positions;
+        }
 
-        let ctx = HeuristicContext {
-            positions: &positions,
-            size: self.size,
-            states: &self.states,
-            colors_with_queens: &self.colors_with_queens,
-            colors: &self.colors,
-            color_masks: &self.color_masks,
+        let ctx = HeuristicContext::new(self, &positions);
+
+        // without a heuristic every placement ties
+        let scored = match &self.heuristic {
+            Some(heuristic) => heuristic.score(&ctx),
+            None => positions.iter().map(|&pos| (pos, 0.0)).collect(),
         };
 
-        let mut scored = heuristic_fn(&ctx);
+        sort_placements(scored, &ctx, &self.tie_break)
+    }
+
+    /// Empty cells of the row, column or region that still needs a queen and
+    /// has the fewest empty cells. Every solution puts a queen on one of them,
+    /// so branching over these cells alone still covers every solution.
+    pub(crate) fn most_constrained_unit(&self) -> Vec<(usize, usize)> {
+        let regions = self.region_count();
+        let mut row_empty = vec![0usize; self.rows];
+        let mut col_empty = vec![0usize; self.cols];
+        let mut region_empty = vec![0usize; regions];
+        let mut row_queens = vec![0usize; self.rows];
+        let mut col_queens = vec![0usize; self.cols];
+        let mut region_queens = vec![0usize; regions];
+
+        for (idx, state) in self.states.iter().enumerate() {
+            let (r, c) = self.idx_to_pos(idx);
+            match state {
+                CellState::Empty => {
+                    row_empty[r] += 1;
+                    col_empty[c] += 1;
+                    region_empty[self.color_at_idx(idx)] += 1;
+                }
+                CellState::Queen => {
+                    row_queens[r] += 1;
+                    col_queens[c] += 1;
+                    region_queens[self.color_at_idx(idx)] += 1;
+                }
+                CellState::Blocked => {}
+            }
+        }
+
+        let row_targets: Vec<usize> = (0..self.rows).map(|r| self.rules.row_target(r)).collect();
+        let col_targets: Vec<usize> = (0..self.cols).map(|c| self.rules.col_target(c)).collect();
+        let region_targets = vec![self.rules.queens_per_unit; regions];
+
+        let units = [
+            (&row_empty, &row_queens, &row_targets),
+            (&col_empty, &col_queens, &col_targets),
+            (&region_empty, &region_queens, &region_targets),
+        ];
+
+        // a unit without room for its missing queens has no solutions
+        let dead = units.iter().any(|(empty, queens, targets)| {
+            (0..empty.len()).any(|i| queens[i] < targets[i] && empty[i] < targets[i] - queens[i])
+        });
+        if dead {
+            return Vec::new();
+        }
+
+        let candidates = units.map(|(empty, queens, targets)| {
+            (0..empty.len())
+                .filter(|&i| queens[i] < targets[i])
+                .min_by_key(|&i| empty[i])
+                .map(|i| (empty[i], i))
+        });
 
-        scored.sort_unstable_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+        let Some((unit, (_, id))) = candidates
+            .into_iter()
+            .enumerate()
+            .filter_map(|(unit, best)| best.map(|best| (unit, best)))
+            .min_by_key(|&(_, (empty, _))| empty)
+        else {
+            return Vec::new();
+        };
 
-        scored.into_iter().map(|(pos, _)| pos).collect()
+        self.states
+            .iter()
+            .enumerate()
+            .filter(|&(idx, state)| {
+                let (r, c) = self.idx_to_pos(idx);
+                *state == CellState::Empty
+                    && match unit {
+                        0 => r == id,
+                        1 => c == id,
+                        _ => self.color_at_idx(idx) == id,
+                    }
+            })
+            .map(|(idx, _)| self.idx_to_pos(idx))
+            .collect()
     }
 
     #[inline]
@@ -222,76 +596,40 @@ impl GameState {
         })
     }
 
-    fn can_place_queen(
+    /// Whether a queen fits on `(r, c)` under the region-level lookahead,
+    /// regardless of the depth `place_queen` uses.
+    #[cfg(test)]
+    pub(crate) fn can_place_queen(
         &self,
         states: &[CellState],
         colors_with_queens: &[bool],
         r: usize,
         c: usize,
     ) -> bool {
+        let mut lookahead = self.lookahead(states, colors_with_queens);
         let idx = self.pos_to_idx(r, c);
+        self.passes_lookahead(&mut lookahead, states, idx, LookaheadDepth::Regions)
+    }
 
-        if states[idx] != CellState::Empty {
-            return false;
-        }
-
-        // check if color already has a queen
-        let queen_color = self.color_at_idx(idx);
-        if colors_with_queens[queen_color as usize] {
-            return false;
-        }
-
-        // 1-step lookahead
-        let mut will_be_blocked = vec![false; self.size * self.size];
-
-        // block row and col
-        for i in 0..self.size {
-            will_be_blocked[self.pos_to_idx(r, i)] = true;
-            will_be_blocked[self.pos_to_idx(i, c)] = true;
-        }
-
-        // block neighbors
-        for (dr, dc) in NEIGHBOR_DISPLACEMENTS {
-            let nr = r as i32 + dr;
-            let nc = c as i32 + dc;
-
-            if self.in_bounds(nr, nc) {
-                let neighbor_idx = self.pos_to_idx(nr as usize, nc as usize);
-                will_be_blocked[neighbor_idx] = true;
-            }
-        }
-
-        // block color region
-        let color_mask = self.get_color_mask(queen_color);
-        for idx in 0..will_be_blocked.len() {
-            if color_mask[idx] {
-                will_be_blocked[idx] = true;
-            }
-        }
-
-        // all other regions must have at least one valid empty cell OR a queen placed already
-        for color in 0..self.size {
-            // skip own color and colors that already have queens
-            if color == queen_color as usize || colors_with_queens[color] {
-                continue;
-            }
-
-            let mut region_has_valid_empty = false;
-            let color_mask = self.get_color_mask(color as u8);
-
-            for idx in 0..states.len() {
-                if states[idx] == CellState::Empty && color_mask[idx] && !will_be_blocked[idx] {
-                    region_has_valid_empty = true;
-                    break;
-                }
-            }
-
-            if !region_has_valid_empty {
-                return false;
-            }
-        }
+    /// Empty cells where a queen fails the region-level lookahead, all checked
+    /// against one `Lookahead` of the current state.
+    pub(crate) fn cells_failing_lookahead(&self) -> Vec<usize> {
+        let mut lookahead = self.lookahead(&self.states, &self.colors_with_queens);
+        (0..self.states.len())
+            .filter(|&idx| {
+                self.states[idx] == CellState::Empty
+                    && !self.passes_lookahead(
+                        &mut lookahead,
+                        &self.states,
+                        idx,
+                        LookaheadDepth::Regions,
+                    )
+            })
+            .collect()
+    }
 
-        true
+    fn attack_table(&self) -> AttackTable {
+        AttackTable::new(self.rows * self.cols, |idx| self.adjacent_cells(idx))
     }
 }
 
@@ -299,102 +637,124 @@ impl TryFrom<Vec<Vec<u8>>> for GameState {
     type Error = GameStateError;
 
     fn try_from(color_regions: Vec<Vec<u8>>) -> Result<Self, Self::Error> {
-        let size = color_regions.len();
+        GameState::from_region_ids(color_regions, RuleSet::default())
+    }
+}
+
+impl TryFrom<Vec<Vec<u16>>> for GameState {
+    type Error = GameStateError;
 
-        if size == 0 {
+    fn try_from(color_regions: Vec<Vec<u16>>) -> Result<Self, Self::Error> {
+        GameState::from_region_ids(color_regions, RuleSet::default())
+    }
+}
+
+impl GameState {
+    fn from_region_ids<T: RegionId>(
+        color_regions: Vec<Vec<T>>,
+        rules: RuleSet,
+    ) -> Result<Self, GameStateError> {
+        let rows = color_regions.len();
+        let cols = color_regions.first().map_or(0, |row| row.len());
+
+        if rows == 0 || cols == 0 {
             return Err(GameStateError::InexistentBoard);
         }
 
-        if size > MAX_BOARD_SIZE {
+        if rows.max(cols) > T::MAX_BOARD_SIZE {
             return Err(GameStateError::BoardTooLarge {
-                size,
-                max_size: MAX_BOARD_SIZE,
+                size: rows.max(cols),
+                max_size: T::MAX_BOARD_SIZE,
             });
         }
 
-        // validate square board
-        for row in color_regions.iter() {
-            if row.len() != size {
-                return Err(GameStateError::NonSquareBoard {
-                    rows: size,
-                    cols: row.len(),
+        // validate rectangular board
+        for (r, row) in color_regions.iter().enumerate() {
+            if row.len() != cols {
+                return Err(GameStateError::RaggedBoard {
+                    row: r,
+                    expected: cols,
+                    found: row.len(),
                 });
             }
         }
 
         // normalize board to be continous from 0
-        let color_regions = normalize_colors(color_regions);
+        let (regions, region_count) = normalize_colors(color_regions, rules.void_cells);
 
-        let total_cells = size * size;
-        let states = vec![CellState::Empty; total_cells];
-        let hash = compute_hash(&states);
-
-        let colors: Vec<u8> = color_regions.into_iter().flatten().collect();
-        if colors.len() != total_cells {
+        let total_cells = rows * cols;
+        if regions.len() != total_cells {
             return Err(GameStateError::InvalidCellCount {
                 expected: total_cells,
-                found: colors.len(),
+                found: regions.len(),
             });
         }
 
-        // collect unique colors
-        let mut unique_colors = HashSet::with_capacity(size);
-        for &color in &colors {
-            unique_colors.insert(color);
-        }
-        let mut unique_colors: Vec<u8> = unique_colors.into_iter().collect();
-        unique_colors.sort_unstable();
-
-        // build color masks as flat vector
-        let total_mask_size = size * size * size;
-        let mut flat_masks = Vec::with_capacity(total_mask_size);
-
-        for &color in &unique_colors {
-            for &cell_color in colors.iter() {
-                flat_masks.push(cell_color == color);
-            }
-        }
+        // void cells start out blocked and never take a queen
+        let states: Vec<CellState> = regions
+            .iter()
+            .map(|region| match region {
+                Some(_) => CellState::Empty,
+                None => CellState::Blocked,
+            })
+            .collect();
+        let hash = compute_hash(&states);
 
-        let color_masks: Rc<[bool]> = Rc::from(flat_masks.into_boxed_slice());
-        let colors_with_queens = vec![false; size];
-        let colors: Rc<[u8]> = Rc::from(colors);
+        let region_index = Rc::new(RegionIndex::new(&regions, region_count));
+        let colors_with_queens = vec![false; region_count];
+        let colors = ColorGrid::new(&regions, region_count);
 
-        Ok(GameState {
-            size,
+        let mut state = GameState {
+            rows,
+            cols,
             states,
             colors_with_queens,
-            color_masks,
             colors,
+            region_index,
+            attacks: Rc::new(AttackTable::new(0, |_| Vec::new())),
+            rules: Rc::new(RuleSet::default()),
             heuristic: None,
+            tie_break: TieBreak::default(),
+            lookahead: LookaheadDepth::default(),
             hash,
-        })
+        };
+        state.validate_rules(&rules)?;
+        state.rules = Rc::new(rules);
+        state.attacks = Rc::new(state.attack_table());
+        Ok(state)
     }
 }
 
-fn normalize_colors(color_regions: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
-    let mut unique_colors: Vec<u8> = color_regions
+/// Flattens the board into region indices starting from 0, with `None` for
+/// void cells, and returns them with the number of regions. `T::VOID` is an
+/// ordinary color unless `void_cells` is set.
+fn normalize_colors<T: RegionId>(
+    color_regions: Vec<Vec<T>>,
+    void_cells: bool,
+) -> (Vec<Option<usize>>, usize) {
+    let mut unique_colors: Vec<T> = color_regions
         .iter()
         .flat_map(|row| row.iter().copied())
-        .collect::<std::collections::HashSet<_>>()
+        .filter(|&color| !void_cells || color != T::VOID)
+        .collect::<HashSet<_>>()
         .into_iter()
         .collect();
 
     unique_colors.sort_unstable();
 
-    let color_map: HashMap<u8, u8> = unique_colors
-        .into_iter()
+    let color_map: HashMap<T, usize> = unique_colors
+        .iter()
         .enumerate()
-        .map(|(new_color, old_color)| (old_color, new_color as u8))
+        .map(|(new_color, &old_color)| (old_color, new_color))
         .collect();
 
-    color_regions
+    let regions = color_regions
         .into_iter()
-        .map(|row| {
-            row.into_iter()
-                .map(|color| *color_map.get(&color).unwrap_or(&0))
-                .collect()
-        })
-        .collect()
+        .flatten()
+        .map(|color| color_map.get(&color).copied())
+        .collect();
+
+    (regions, unique_colors.len())
 }
 
 impl Hash for GameState {
@@ -405,7 +765,7 @@ impl Hash for GameState {
     }
 }
 
-fn compute_hash(states: &[CellState]) -> u64 {
+pub(crate) fn compute_hash(states: &[CellState]) -> u64 {
     // only states change over the course of a dfs
     // all other attributes are either static or derived from states
     let mut hasher = DefaultHasher::new();
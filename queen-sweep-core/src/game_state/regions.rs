@@ -0,0 +1,59 @@
+/// Cell indices of every region, stored back to back in one buffer.
+///
+/// Takes one entry per board cell, instead of one boolean per cell and region.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionIndex {
+    // cells of region `i` are `cells[offsets[i]..offsets[i + 1]]`
+    offsets: Box<[usize]>,
+    cells: Box<[usize]>,
+}
+
+impl RegionIndex {
+    /// Builds the index from the region of every cell, `None` marking void cells.
+    pub(crate) fn new(regions: &[Option<usize>], region_count: usize) -> Self {
+        let mut offsets = vec![0; region_count + 1];
+        for region in regions.iter().flatten() {
+            offsets[region + 1] += 1;
+        }
+        for i in 0..region_count {
+            offsets[i + 1] += offsets[i];
+        }
+
+        let mut next = offsets.clone();
+        let mut cells = vec![0; offsets[region_count]];
+        for (idx, region) in regions.iter().enumerate() {
+            if let Some(region) = *region {
+                cells[next[region]] = idx;
+                next[region] += 1;
+            }
+        }
+
+        RegionIndex {
+            offsets: offsets.into_boxed_slice(),
+            cells: cells.into_boxed_slice(),
+        }
+    }
+
+    /// Number of regions.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Cell indices of `region`, in row-major order.
+    #[inline]
+    pub fn cells(&self, region: usize) -> &[usize] {
+        &self.cells[self.offsets[region]..self.offsets[region + 1]]
+    }
+
+    /// Number of cells in `region`.
+    #[inline]
+    pub fn region_size(&self, region: usize) -> usize {
+        self.offsets[region + 1] - self.offsets[region]
+    }
+}
@@ -1,21 +1,19 @@
-use crate::{GameState, GameStateError, depth_first_search, game_state::MAX_BOARD_SIZE, heuristic};
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
 
-fn puzzle_8x8_sol() -> Vec<Vec<u8>> {
-    // Solution Exists
-    vec![
-        vec![0, 0, 1, 1, 1, 2, 2, 2],
-        vec![0, 3, 1, 3, 1, 4, 2, 2],
-        vec![0, 3, 1, 3, 1, 2, 2, 2],
-        vec![0, 3, 3, 3, 1, 5, 6, 2],
-        vec![0, 3, 3, 3, 1, 5, 6, 6],
-        vec![0, 3, 7, 3, 1, 5, 6, 6],
-        vec![7, 3, 7, 3, 1, 5, 5, 6],
-        vec![7, 7, 7, 7, 6, 6, 6, 6],
-    ]
-}
+use crate::fixtures::{puzzle_3x3_nosol, puzzle_8x8_sol, row_regions};
+use crate::{
+    Adjacency, CellState, ColorGrid, GameState, GameStateError, LookaheadDepth, RuleSet,
+    VOID_COLOR, count_solutions, depth_first_search, game_state::MAX_BOARD_SIZE, heuristic,
+};
 
-fn puzzle_3x3_nosol() -> Vec<Vec<u8>> {
-    vec![vec![0, 0, 1], vec![1, 1, 2], vec![2, 2, 2]]
+fn void_rules() -> RuleSet {
+    RuleSet {
+        void_cells: true,
+        ..RuleSet::default()
+    }
 }
 
 fn get_initial_game_state_8x8() -> GameState {
@@ -45,21 +43,25 @@ fn test_gamestate_empty_board() {
 }
 
 #[test]
-fn test_gamestate_non_square_board() {
-    let puzzle = vec![vec![0, 1, 2], vec![0, 1]];
+fn test_gamestate_ragged_board() {
+    let puzzle: Vec<Vec<u8>> = vec![vec![0, 1, 2], vec![0, 1]];
     let state = GameState::try_from(puzzle);
 
     assert!(state.is_err());
     assert!(matches!(
         state.unwrap_err(),
-        GameStateError::NonSquareBoard { .. }
+        GameStateError::RaggedBoard {
+            row: 1,
+            expected: 3,
+            found: 2
+        }
     ));
 }
 
 #[test]
 fn test_gamestate_board_too_large() {
     let size = MAX_BOARD_SIZE + 1;
-    let puzzle = vec![vec![0; size as usize]; size as usize];
+    let puzzle: Vec<Vec<u8>> = vec![vec![0; size]; size];
     let state = GameState::try_from(puzzle);
 
     assert!(state.is_err());
@@ -111,3 +113,477 @@ fn test_3x3_has_no_solution() {
     let (solved_state, _) = depth_first_search(state);
     assert!(solved_state.is_none());
 }
+
+fn block_regions(size: usize, block_rows: usize, block_cols: usize) -> Vec<Vec<u8>> {
+    let blocks_per_row = size / block_cols;
+    (0..size)
+        .map(|r| {
+            (0..size)
+                .map(|c| ((r / block_rows) * blocks_per_row + c / block_cols) as u8)
+                .collect()
+        })
+        .collect()
+}
+
+/// Counts boards with `k` non-touching queens in every row, column and region.
+fn brute_force_star_battle(regions: &[Vec<u8>], k: usize) -> usize {
+    fn place(regions: &[Vec<u8>], k: usize, row: usize, queens: &mut Vec<(usize, usize)>) -> usize {
+        let size = regions.len();
+        if row == size {
+            let full = |unit: &dyn Fn(&(usize, usize)) -> usize| {
+                (0..size).all(|i| queens.iter().filter(|q| unit(q) == i).count() == k)
+            };
+            return (full(&|q| q.1) && full(&|q| regions[q.0][q.1] as usize)) as usize;
+        }
+
+        let mut total = 0;
+        let mut cols = vec![0; k];
+        loop {
+            let valid = cols.windows(2).all(|w| w[0] + 1 < w[1])
+                && cols.iter().all(|&c| {
+                    queens
+                        .iter()
+                        .all(|&(qr, qc)| qr + 1 < row || qc.abs_diff(c) > 1)
+                });
+            if valid {
+                queens.extend(cols.iter().map(|&c| (row, c)));
+                total += place(regions, k, row + 1, queens);
+                queens.truncate(queens.len() - k);
+            }
+
+            // next combination of k columns
+            let Some(i) = (0..k).rev().find(|&i| cols[i] < size - 1) else {
+                break;
+            };
+            cols[i] += 1;
+            for j in i + 1..k {
+                cols[j] = cols[i];
+            }
+        }
+        total
+    }
+
+    place(regions, k, 0, &mut Vec::new())
+}
+
+#[test]
+fn test_star_battle_rejects_invalid_queens_per_unit() {
+    let state = GameState::from_color_regions_with_rules(
+        block_regions(4, 2, 2),
+        None,
+        RuleSet::star_battle(0),
+    );
+
+    assert!(matches!(
+        state,
+        Err(GameStateError::InvalidQueensPerUnit {
+            queens_per_unit: 0,
+            size: 4
+        })
+    ));
+}
+
+#[test]
+fn test_overfull_lines_name_the_line() {
+    let state = GameState::from_color_regions_with_rules(
+        block_regions(4, 2, 2),
+        None,
+        RuleSet::star_battle(3),
+    );
+    assert!(matches!(
+        state,
+        Err(GameStateError::RowOverfull {
+            row: 0,
+            queens: 3,
+            len: 4
+        })
+    ));
+
+    let rules = RuleSet {
+        col_queens: Some(vec![1, 1, 3, 1]),
+        ..RuleSet::default()
+    };
+    let state = GameState::from_color_regions_with_rules(row_regions(4), None, rules);
+    assert!(matches!(
+        state,
+        Err(GameStateError::ColumnOverfull {
+            col: 2,
+            queens: 3,
+            len: 4
+        })
+    ));
+}
+
+#[test]
+fn test_two_star_solution_count_matches_brute_force() {
+    let regions = block_regions(8, 2, 4);
+    let state =
+        GameState::from_color_regions_with_rules(regions.clone(), None, RuleSet::star_battle(2))
+            .unwrap();
+
+    let expected = brute_force_star_battle(&regions, 2);
+    assert!(expected > 0);
+    assert_eq!(count_solutions(state, None), expected);
+}
+
+#[test]
+fn test_two_star_10x10_has_solution() {
+    let regions = block_regions(10, 2, 5);
+    let state = GameState::from_color_regions_with_rules(
+        regions.clone(),
+        Some(heuristic::smallest_region_by_empty_cells),
+        RuleSet::star_battle(2),
+    )
+    .unwrap();
+
+    let (solution, _) = depth_first_search(state);
+    let solution = solution.expect("2-star layout is solvable");
+    let queens: Vec<(usize, usize)> = solution.queen_positions().collect();
+
+    assert_eq!(queens.len(), 20);
+    for i in 0..10 {
+        assert_eq!(queens.iter().filter(|q| q.0 == i).count(), 2);
+        assert_eq!(queens.iter().filter(|q| q.1 == i).count(), 2);
+        assert_eq!(
+            queens
+                .iter()
+                .filter(|q| regions[q.0][q.1] as usize == i)
+                .count(),
+            2
+        );
+    }
+    for (i, a) in queens.iter().enumerate() {
+        for b in &queens[i + 1..] {
+            assert!(a.0.abs_diff(b.0) > 1 || a.1.abs_diff(b.1) > 1);
+        }
+    }
+}
+
+#[test]
+fn test_adjacency_rules_block_neighbors() {
+    let blocked = |adjacency: Adjacency| {
+        let rules = RuleSet {
+            adjacency,
+            ..RuleSet::star_battle(2)
+        };
+        let state = GameState::from_color_regions_with_rules(row_regions(8), None, rules)
+            .unwrap()
+            .place_queen(3, 3);
+        let cell = |r: usize, c: usize| state.states()[r * 8 + c] == CellState::Blocked;
+        (cell(3, 4), cell(4, 4))
+    };
+
+    assert_eq!(blocked(Adjacency::King), (true, true));
+    assert_eq!(blocked(Adjacency::Orthogonal), (true, false));
+    assert_eq!(blocked(Adjacency::None), (false, false));
+}
+
+#[test]
+fn test_n_queens_rules_match_known_counts() {
+    // with one region per row only the N-queens constraints remain
+    for (size, expected) in [(4, 2), (5, 10), (6, 4), (8, 92)] {
+        let state =
+            GameState::from_color_regions_with_rules(row_regions(size), None, RuleSet::n_queens())
+                .unwrap();
+        assert_eq!(count_solutions(state, None), expected, "size {size}");
+    }
+}
+
+#[test]
+fn test_rectangular_board_with_line_counts() {
+    // 4x8 board of 2x2 regions: two queens per row, one per column and region
+    let regions: Vec<Vec<u8>> = (0..4)
+        .map(|r| (0..8).map(|c| (r / 2 * 4 + c / 2) as u8).collect())
+        .collect();
+    let rules = RuleSet {
+        row_queens: Some(vec![2; 4]),
+        col_queens: Some(vec![1; 8]),
+        ..RuleSet::default()
+    };
+    let state = GameState::from_color_regions_with_rules(regions, None, rules.clone()).unwrap();
+
+    assert_eq!(
+        (state.rows(), state.cols(), state.region_count()),
+        (4, 8, 8)
+    );
+    assert_eq!(count_solutions(state.clone(), None), 2);
+
+    let (solution, _) = depth_first_search(state);
+    let queens: Vec<(usize, usize)> = solution.unwrap().queen_positions().collect();
+    for r in 0..4 {
+        assert_eq!(queens.iter().filter(|q| q.0 == r).count(), 2);
+    }
+    for c in 0..8 {
+        assert_eq!(queens.iter().filter(|q| q.1 == c).count(), 1);
+    }
+
+    let short_rows = RuleSet {
+        row_queens: Some(vec![2; 3]),
+        ..rules.clone()
+    };
+    assert!(matches!(
+        GameState::from_color_regions_with_rules(block_regions(8, 2, 2), None, short_rows),
+        Err(GameStateError::QueenCountLength { .. })
+    ));
+    assert!(matches!(
+        GameState::from_color_regions_with_rules(
+            row_regions(4),
+            None,
+            RuleSet {
+                row_queens: Some(vec![1, 1, 1, 2]),
+                ..RuleSet::default()
+            }
+        ),
+        Err(GameStateError::QueenCountMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_void_cells_never_take_queens() {
+    let corners = [(0, 0), (0, 4), (4, 0), (4, 4)];
+    let mut regions = row_regions(5);
+    for &(r, c) in &corners {
+        regions[r][c] = VOID_COLOR;
+    }
+
+    let shaped = GameState::from_color_regions_with_rules(regions, None, void_rules()).unwrap();
+    for &(r, c) in &corners {
+        assert_eq!(shaped.states()[r * 5 + c], CellState::Blocked);
+    }
+
+    // voids behave like cells that are blocked from the start
+    let blocked = GameState::try_from(row_regions(5))
+        .unwrap()
+        .block_cells(&corners);
+    let expected = count_solutions(blocked, None);
+    assert!(expected > 0);
+    assert_eq!(count_solutions(shaped.clone(), None), expected);
+
+    let (solution, _) = depth_first_search(shaped);
+    let solution = solution.unwrap();
+    assert!(
+        solution
+            .queen_positions()
+            .all(|queen| !corners.contains(&queen))
+    );
+}
+
+#[test]
+fn test_toroidal_adjacency_wraps_around_edges() {
+    let blocked = |toroidal: bool| {
+        let rules = RuleSet {
+            toroidal,
+            ..RuleSet::star_battle(2)
+        };
+        let state = GameState::from_color_regions_with_rules(row_regions(8), None, rules)
+            .unwrap()
+            .place_queen(0, 0);
+        let cell = |r: usize, c: usize| state.states()[r * 8 + c] == CellState::Blocked;
+        (cell(0, 7), cell(7, 7))
+    };
+
+    assert_eq!(blocked(false), (false, false));
+    assert_eq!(blocked(true), (true, true));
+}
+
+#[test]
+fn test_toroidal_n_queens_match_known_counts() {
+    // modular N-queens has solutions only when the size is coprime to 6
+    for (size, expected) in [(5, 10), (6, 0), (7, 28)] {
+        let rules = RuleSet {
+            toroidal: true,
+            ..RuleSet::n_queens()
+        };
+        let state =
+            GameState::from_color_regions_with_rules(row_regions(size), None, rules).unwrap();
+        assert_eq!(count_solutions(state, None), expected, "size {size}");
+    }
+}
+
+#[test]
+fn test_wide_region_ids_match_narrow() {
+    let narrow = puzzle_8x8_sol();
+    let wide: Vec<Vec<u16>> = narrow
+        .iter()
+        .map(|row| row.iter().map(|&color| color as u16 * 1000).collect())
+        .collect();
+
+    let narrow = GameState::try_from(narrow).unwrap();
+    let wide = GameState::try_from(wide).unwrap();
+
+    // normalized ids fit in a byte, so the compact grid is kept
+    assert!(matches!(wide.colors(), ColorGrid::Narrow(_)));
+    assert_eq!(narrow.colors().to_ids::<u8>(), wide.colors().to_ids::<u8>());
+    assert_eq!(
+        depth_first_search(narrow).0.map(|s| s.states().to_vec()),
+        depth_first_search(wide).0.map(|s| s.states().to_vec()),
+    );
+}
+
+#[test]
+fn test_more_than_255_regions() {
+    // one row of single-cell regions, every cell takes a queen
+    let cols = 300;
+    let regions = vec![(0..cols as u16).collect::<Vec<_>>()];
+    let rules = RuleSet {
+        row_queens: Some(vec![cols]),
+        adjacency: Adjacency::None,
+        forbid_diagonals: false,
+        ..RuleSet::default()
+    };
+
+    let state = GameState::from_color_regions_with_rules(regions, None, rules).unwrap();
+    assert_eq!(state.region_count(), cols);
+    assert!(matches!(state.colors(), ColorGrid::Wide(_)));
+    assert_eq!(state.colors().to_ids::<u8>(), None);
+
+    let placed = state.place_queen(0, cols - 1);
+    assert!(placed.colors_with_queens()[cols - 1]);
+    assert_eq!(placed.states()[cols - 2], CellState::Empty);
+}
+
+#[test]
+fn test_void_color_is_a_region_by_default() {
+    let regions = vec![vec![0, VOID_COLOR], vec![VOID_COLOR, 0]];
+
+    let state = GameState::try_from(regions.clone()).unwrap();
+    assert_eq!(state.region_count(), 2);
+    assert!(!state.colors().is_void(1));
+    assert_eq!(state.states()[1], CellState::Empty);
+
+    // as holes, the two remaining cells form a single region for two rows
+    assert!(matches!(
+        GameState::from_color_regions_with_rules(regions, None, void_rules()),
+        Err(GameStateError::QueenCountMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_default_rules_are_validated() {
+    // every constructor checks the queen counts of the default rules
+    let single_row = vec![vec![0u8, 1, 2]];
+    assert!(matches!(
+        GameState::from_color_regions(single_row.clone(), None),
+        Err(GameStateError::QueenCountMismatch { .. })
+    ));
+    assert!(GameState::try_from(single_row).is_err());
+
+    let two_regions = vec![vec![0u8, 0, 1], vec![0, 1, 1]];
+    assert!(matches!(
+        GameState::try_from(two_regions.clone()),
+        Err(GameStateError::QueenCountMismatch { .. })
+    ));
+    assert!(GameState::from_color_regions(two_regions, None).is_err());
+}
+
+#[test]
+fn test_wide_void_cells() {
+    let mut regions: Vec<Vec<u16>> = row_regions(4)
+        .into_iter()
+        .map(|row| row.into_iter().map(u16::from).collect())
+        .collect();
+    regions[0][0] = u16::MAX;
+
+    let state = GameState::from_color_regions_with_rules(regions, None, void_rules()).unwrap();
+    assert!(state.colors().is_void(0));
+    assert_eq!(state.states()[0], CellState::Blocked);
+    assert_eq!(state.region_count(), 4);
+}
+
+#[test]
+fn test_region_index_lists_region_cells() {
+    let mut regions = puzzle_8x8_sol();
+    regions[7][7] = VOID_COLOR;
+    let state = GameState::from_color_regions_with_rules(regions, None, void_rules()).unwrap();
+    let index = state.region_index();
+
+    assert_eq!(index.len(), state.region_count());
+    for region in 0..index.len() {
+        let expected: Vec<usize> = (0..64)
+            .filter(|&idx| state.colors().region(idx) == Some(region))
+            .collect();
+        assert_eq!(index.cells(region), expected.as_slice());
+        assert_eq!(index.region_size(region), expected.len());
+    }
+    assert!(!(0..index.len()).any(|region| index.cells(region).contains(&63)));
+}
+
+#[test]
+fn test_line_lookahead_blocks_confined_regions() {
+    // regions 0 and 2 both need their queen in the last row, which only takes one
+    let regions: Vec<Vec<u8>> = vec![
+        vec![3, 3, 3, 3, 3],
+        vec![4, 4, 4, 4, 4],
+        vec![4, 4, 4, 4, 4],
+        vec![1, 1, 1, 1, 1],
+        vec![0, 0, 1, 2, 2],
+    ];
+    let empty_cells = |depth: LookaheadDepth| {
+        let state = GameState::try_from(regions.clone())
+            .unwrap()
+            .with_lookahead(depth);
+        state
+            .place_queen(2, 0)
+            .states()
+            .iter()
+            .filter(|&&cell| cell == CellState::Empty)
+            .count()
+    };
+
+    assert!(empty_cells(LookaheadDepth::Regions) > 0);
+    assert_eq!(empty_cells(LookaheadDepth::Lines), 0);
+}
+
+#[test]
+fn test_lookahead_depths_agree_on_solutions() {
+    let regions = puzzle_8x8_sol();
+    for depth in [
+        LookaheadDepth::Disabled,
+        LookaheadDepth::Regions,
+        LookaheadDepth::Lines,
+    ] {
+        let state = GameState::try_from(regions.clone())
+            .unwrap()
+            .with_lookahead(depth);
+        assert_eq!(count_solutions(state, None), 1);
+    }
+}
+
+#[test]
+fn test_heuristic_trait_matches_fn_heuristic() {
+    let by_fn = GameState::from_color_regions(
+        puzzle_8x8_sol(),
+        Some(heuristic::smallest_region_by_empty_cells),
+    )
+    .unwrap();
+    let by_trait = GameState::try_from(puzzle_8x8_sol())
+        .unwrap()
+        .with_heuristic(heuristic::RegionRowScarcity { region_weight: 1.0 });
+
+    assert_eq!(by_fn.valid_placements(), by_trait.valid_placements());
+    assert_eq!(depth_first_search(by_fn), depth_first_search(by_trait));
+}
+
+#[test]
+fn test_heuristic_reset_before_search() {
+    struct Counting(AtomicUsize);
+
+    impl heuristic::Heuristic for Counting {
+        fn score(&self, ctx: &heuristic::HeuristicContext) -> Vec<((usize, usize), f32)> {
+            ctx.positions.iter().map(|&pos| (pos, 0.0)).collect()
+        }
+
+        fn reset(&self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let counting = Arc::new(Counting(AtomicUsize::new(0)));
+    let state = GameState::try_from(puzzle_8x8_sol())
+        .unwrap()
+        .with_heuristic(Arc::clone(&counting));
+    depth_first_search(state.clone());
+    depth_first_search(state);
+
+    assert_eq!(counting.0.load(Ordering::Relaxed), 2);
+}
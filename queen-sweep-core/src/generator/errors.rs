@@ -0,0 +1,42 @@
+use crate::Difficulty;
+
+#[derive(Debug)]
+pub enum GeneratorError {
+    UnsupportedSize { size: usize },
+
+    UnsupportedRules,
+
+    AttemptsExhausted { attempts: usize },
+
+    DifficultyNotReached { target: Difficulty, attempts: usize },
+}
+
+impl std::fmt::Display for GeneratorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedSize { size } => {
+                write!(f, "No queen layout exists for a board of size {}", size)
+            }
+            Self::UnsupportedRules => {
+                write!(
+                    f,
+                    "Only one queen per row, column and region can be generated"
+                )
+            }
+            Self::AttemptsExhausted { attempts } => {
+                write!(
+                    f,
+                    "Failed to generate a uniquely solvable board in {} attempts",
+                    attempts
+                )
+            }
+            Self::DifficultyNotReached { target, attempts } => {
+                write!(
+                    f,
+                    "Failed to generate a {:?} board in {} attempts",
+                    target, attempts
+                )
+            }
+        }
+    }
+}
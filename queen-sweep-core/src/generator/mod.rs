@@ -0,0 +1,294 @@
+mod errors;
+pub use errors::GeneratorError;
+
+mod rng;
+pub use rng::SeededRng;
+
+use std::collections::VecDeque;
+
+use crate::{
+    Branching, CellState, Difficulty, DifficultyReport, GameState, RuleSet, SolveOptions,
+    enumerate_solutions, game_state::MAX_BOARD_SIZE, rate_difficulty,
+};
+
+const MAX_ATTEMPTS: usize = 64;
+const DEFAULT_DIFFICULTY_ATTEMPTS: usize = 256;
+const ORTHOGONAL_DISPLACEMENTS: [(i32, i32); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Generates a uniquely solvable board of the given size.
+///
+/// A random queen layout is planted, a connected region is grown around every
+/// queen, and cells are then moved between regions until the planted layout is
+/// the only solution left.
+pub fn generate_board(size: usize, rng: &mut SeededRng) -> Result<Vec<Vec<u8>>, GeneratorError> {
+    generate_board_with_rules(size, &RuleSet::default(), rng)
+}
+
+/// Generates a board that is uniquely solvable under `rules`.
+/// Only rules with one queen per row, column and region are supported.
+pub fn generate_board_with_rules(
+    size: usize,
+    rules: &RuleSet,
+    rng: &mut SeededRng,
+) -> Result<Vec<Vec<u8>>, GeneratorError> {
+    if !rules.is_single_queen() {
+        return Err(GeneratorError::UnsupportedRules);
+    }
+
+    if !(1..=MAX_BOARD_SIZE).contains(&size) {
+        return Err(GeneratorError::UnsupportedSize { size });
+    }
+
+    for _ in 0..MAX_ATTEMPTS {
+        let queens = random_queen_layout(size, rules, rng)
+            .ok_or(GeneratorError::UnsupportedSize { size })?;
+        let mut colors = grow_regions(size, &queens, rng);
+
+        if make_unique(size, rules, &queens, &mut colors, rng) {
+            return Ok(colors.chunks(size).map(|row| row.to_vec()).collect());
+        }
+    }
+
+    Err(GeneratorError::AttemptsExhausted {
+        attempts: MAX_ATTEMPTS,
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct GenerateOptions {
+    pub size: usize,
+    pub difficulty: Difficulty,
+    pub seed: u64,
+    /// Number of boards to generate and rate before giving up.
+    pub max_attempts: usize,
+    pub rules: RuleSet,
+}
+
+impl GenerateOptions {
+    pub fn new(size: usize, difficulty: Difficulty, seed: u64) -> Self {
+        GenerateOptions {
+            size,
+            difficulty,
+            seed,
+            max_attempts: DEFAULT_DIFFICULTY_ATTEMPTS,
+            rules: RuleSet::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GeneratedBoard {
+    pub regions: Vec<Vec<u8>>,
+    /// Reproduces `regions` through `generate_board_with_rules` with the same size and rules.
+    pub seed: u64,
+    pub report: DifficultyReport,
+}
+
+/// Generates boards until one is rated at the requested difficulty.
+///
+/// Every attempt draws its own seed from `options.seed`, so the whole run and
+/// the returned board are reproducible.
+pub fn generate_board_with_difficulty(
+    options: &GenerateOptions,
+) -> Result<GeneratedBoard, GeneratorError> {
+    let mut seeds = SeededRng::new(options.seed);
+
+    for _ in 0..options.max_attempts {
+        let seed = seeds.next_u64();
+
+        let mut rng = SeededRng::new(seed);
+        let regions = match generate_board_with_rules(options.size, &options.rules, &mut rng) {
+            Ok(regions) => regions,
+            Err(GeneratorError::AttemptsExhausted { .. }) => continue,
+            Err(e) => return Err(e),
+        };
+
+        let state =
+            GameState::from_color_regions_with_rules(regions.clone(), None, options.rules.clone())
+                .expect("generated boards are valid");
+        let report = rate_difficulty(&state);
+
+        if report.difficulty == options.difficulty {
+            return Ok(GeneratedBoard {
+                regions,
+                seed,
+                report,
+            });
+        }
+    }
+
+    Err(GeneratorError::DifficultyNotReached {
+        target: options.difficulty,
+        attempts: options.max_attempts,
+    })
+}
+
+/// Column of the queen in every row, with no two queens attacking each other
+/// under `rules`. `None` if no such layout exists.
+fn random_queen_layout(size: usize, rules: &RuleSet, rng: &mut SeededRng) -> Option<Vec<usize>> {
+    fn place(state: &GameState, row: usize, rng: &mut SeededRng) -> Option<Vec<usize>> {
+        let (rows, width) = (state.rows(), state.cols());
+        if row == rows {
+            return Some(Vec::with_capacity(rows));
+        }
+
+        let mut cols: Vec<usize> = (0..width)
+            .filter(|&c| state.states()[row * width + c] == CellState::Empty)
+            .collect();
+        rng.shuffle(&mut cols);
+
+        cols.into_iter().find_map(|c| {
+            let mut queens = place(&state.place_queen_without_lookahead(row, c), row + 1, rng)?;
+            queens.insert(0, c);
+            Some(queens)
+        })
+    }
+
+    // with one region per row only the column and attack rules constrain the layout
+    let rows: Vec<Vec<u8>> = (0..size).map(|r| vec![r as u8; size]).collect();
+    let state = GameState::from_color_regions_with_rules(rows, None, rules.clone()).ok()?;
+    place(&state, 0, rng)
+}
+
+/// Grows one region around every queen by repeatedly claiming a random
+/// unassigned cell next to an existing region.
+fn grow_regions(size: usize, queens: &[usize], rng: &mut SeededRng) -> Vec<u8> {
+    let mut colors: Vec<Option<u8>> = vec![None; size * size];
+    for (r, &c) in queens.iter().enumerate() {
+        colors[r * size + c] = Some(r as u8);
+    }
+
+    let mut frontier: Vec<usize> = Vec::new();
+    loop {
+        frontier.clear();
+        frontier.extend((0..size * size).filter(|&idx| {
+            colors[idx].is_none() && neighbors(size, size, idx).any(|n| colors[n].is_some())
+        }));
+
+        if frontier.is_empty() {
+            break;
+        }
+
+        let idx = frontier[rng.below(frontier.len())];
+        let claims: Vec<u8> = neighbors(size, size, idx)
+            .filter_map(|n| colors[n])
+            .collect();
+        colors[idx] = Some(claims[rng.below(claims.len())]);
+    }
+
+    colors.into_iter().map(|color| color.unwrap_or(0)).collect()
+}
+
+/// Moves cells between regions until only the planted layout solves the board.
+/// Returns `false` if no move is left that rules out the competing solution.
+fn make_unique(
+    size: usize,
+    rules: &RuleSet,
+    queens: &[usize],
+    colors: &mut [u8],
+    rng: &mut SeededRng,
+) -> bool {
+    let options = SolveOptions {
+        symmetry_pruning: false,
+        branching: Branching::MostConstrainedUnit,
+        ..SolveOptions::default()
+    };
+    let planted: Vec<usize> = queens
+        .iter()
+        .enumerate()
+        .map(|(r, &c)| r * size + c)
+        .collect();
+    // every cell moves at most once, otherwise moves can undo each other forever
+    let mut moved = vec![false; colors.len()];
+
+    loop {
+        let regions: Vec<Vec<u8>> = colors.chunks(size).map(|row| row.to_vec()).collect();
+        let Ok(state) = GameState::from_color_regions_with_rules(regions, None, rules.clone())
+        else {
+            return false;
+        };
+
+        let (solutions, _) = enumerate_solutions(state, &options, Some(2));
+        if solutions.len() <= 1 {
+            return true;
+        }
+
+        // a queen of the competing solution that is not planted can change region,
+        // which leaves the competing solution with two queens in its new region
+        let competing = solutions
+            .iter()
+            .find(|s| s.queen_positions().any(|(r, c)| queens[r] != c))
+            .expect("planted layout is always a solution");
+
+        let mut cells: Vec<usize> = competing
+            .queen_positions()
+            .map(|(r, c)| r * size + c)
+            .filter(|&idx| !moved[idx] && !planted.contains(&idx))
+            .collect();
+        rng.shuffle(&mut cells);
+
+        let any_moved = cells.into_iter().any(|idx| {
+            let mut targets: Vec<u8> = neighbors(size, size, idx)
+                .map(|n| colors[n])
+                .filter(|&color| color != colors[idx])
+                .collect();
+            rng.shuffle(&mut targets);
+
+            match targets.first() {
+                Some(&target) if stays_connected(size, colors, idx) => {
+                    colors[idx] = target;
+                    moved[idx] = true;
+                    true
+                }
+                _ => false,
+            }
+        });
+
+        if !any_moved {
+            return false;
+        }
+    }
+}
+
+/// Whether the region of `removed` is still connected without it.
+fn stays_connected(size: usize, colors: &[u8], removed: usize) -> bool {
+    let color = colors[removed];
+    let cells: Vec<usize> = (0..colors.len())
+        .filter(|&idx| idx != removed && colors[idx] == color)
+        .collect();
+
+    let Some(&start) = cells.first() else {
+        return false;
+    };
+
+    let mut visited = vec![false; colors.len()];
+    let mut queue = VecDeque::from([start]);
+    visited[start] = true;
+    let mut reached = 1;
+
+    while let Some(idx) = queue.pop_front() {
+        for n in neighbors(size, size, idx) {
+            if n != removed && !visited[n] && colors[n] == color {
+                visited[n] = true;
+                reached += 1;
+                queue.push_back(n);
+            }
+        }
+    }
+
+    reached == cells.len()
+}
+
+pub(crate) fn neighbors(rows: usize, cols: usize, idx: usize) -> impl Iterator<Item = usize> {
+    let (r, c) = ((idx / cols) as i32, (idx % cols) as i32);
+    ORTHOGONAL_DISPLACEMENTS
+        .into_iter()
+        .filter_map(move |(dr, dc)| {
+            let (nr, nc) = (r + dr, c + dc);
+            let in_bounds = nr >= 0 && nc >= 0 && nr < rows as i32 && nc < cols as i32;
+            in_bounds.then(|| nr as usize * cols + nc as usize)
+        })
+}
+
+#[cfg(test)]
+mod test;
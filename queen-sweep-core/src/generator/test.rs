@@ -0,0 +1,138 @@
+use crate::{
+    Adjacency, Difficulty, GameState, GenerateOptions, GeneratorError, RuleSet, SeededRng,
+    count_solutions, generate_board, generate_board_with_difficulty, generate_board_with_rules,
+};
+
+fn assert_regions_connected(regions: &[Vec<u8>]) {
+    let size = regions.len();
+    for color in 0..size as u8 {
+        let cells: Vec<(usize, usize)> = (0..size * size)
+            .map(|idx| (idx / size, idx % size))
+            .filter(|&(r, c)| regions[r][c] == color)
+            .collect();
+        assert!(!cells.is_empty(), "region {color} is empty");
+
+        let mut reached = vec![cells[0]];
+        let mut i = 0;
+        while i < reached.len() {
+            let (r, c) = reached[i];
+            for &(nr, nc) in &cells {
+                if r.abs_diff(nr) + c.abs_diff(nc) == 1 && !reached.contains(&(nr, nc)) {
+                    reached.push((nr, nc));
+                }
+            }
+            i += 1;
+        }
+        assert_eq!(reached.len(), cells.len(), "region {color} is split");
+    }
+}
+
+#[test]
+fn test_generated_boards_are_unique() {
+    for (size, seed) in [(1, 0), (4, 1), (5, 2), (6, 3), (7, 4), (8, 5)] {
+        let regions = generate_board(size, &mut SeededRng::new(seed)).unwrap();
+
+        assert_eq!(regions.len(), size);
+        assert!(regions.iter().all(|row| row.len() == size));
+        assert_regions_connected(&regions);
+
+        let state = GameState::from_color_regions(regions, None).unwrap();
+        assert_eq!(count_solutions(state, None), 1, "size {size}");
+    }
+}
+
+#[test]
+fn test_generation_is_reproducible() {
+    let first = generate_board(7, &mut SeededRng::new(42)).unwrap();
+    let second = generate_board(7, &mut SeededRng::new(42)).unwrap();
+    let other = generate_board(7, &mut SeededRng::new(43)).unwrap();
+
+    assert_eq!(first, second);
+    assert_ne!(first, other);
+}
+
+#[test]
+fn test_generate_unsupported_size() {
+    for size in [0, 2, 3] {
+        let result = generate_board(size, &mut SeededRng::new(0));
+        assert!(matches!(
+            result,
+            Err(GeneratorError::UnsupportedSize { .. })
+        ));
+    }
+}
+
+#[test]
+fn test_small_boards_without_adjacency() {
+    // 2x2 and 3x3 boards only lack solutions while queens may not touch
+    let rules = RuleSet {
+        adjacency: Adjacency::None,
+        ..RuleSet::default()
+    };
+
+    for size in [2, 3] {
+        let regions = generate_board_with_rules(size, &rules, &mut SeededRng::new(0)).unwrap();
+        assert_regions_connected(&regions);
+
+        let state = GameState::from_color_regions_with_rules(regions, None, rules.clone()).unwrap();
+        assert_eq!(count_solutions(state, None), 1, "size {size}");
+    }
+}
+
+#[test]
+fn test_difficulty_target_is_met_and_reproducible() {
+    for difficulty in [Difficulty::Easy, Difficulty::Hard] {
+        let options = GenerateOptions::new(7, difficulty, 11);
+        let board = generate_board_with_difficulty(&options).unwrap();
+
+        assert_eq!(board.report.difficulty, difficulty);
+        assert!(board.report.is_unique());
+
+        let again = generate_board_with_difficulty(&options).unwrap();
+        assert_eq!(again.seed, board.seed);
+
+        let replayed = generate_board(7, &mut SeededRng::new(board.seed)).unwrap();
+        assert_eq!(replayed, board.regions);
+    }
+}
+
+#[test]
+fn test_difficulty_target_respects_attempt_limit() {
+    // a single queen never needs more than the easiest deduction
+    let options = GenerateOptions {
+        max_attempts: 3,
+        ..GenerateOptions::new(1, Difficulty::Hard, 0)
+    };
+
+    assert!(matches!(
+        generate_board_with_difficulty(&options),
+        Err(GeneratorError::DifficultyNotReached { attempts: 3, .. })
+    ));
+}
+
+#[test]
+fn test_toroidal_boards_are_unique() {
+    let rules = RuleSet {
+        toroidal: true,
+        ..RuleSet::default()
+    };
+
+    for (size, seed) in [(5, 1), (7, 2)] {
+        let regions = generate_board_with_rules(size, &rules, &mut SeededRng::new(seed)).unwrap();
+        assert_regions_connected(&regions);
+
+        let state =
+            GameState::from_color_regions_with_rules(regions.clone(), None, rules.clone()).unwrap();
+        assert_eq!(count_solutions(state, None), 1, "size {size}");
+    }
+
+    // no four queens can be placed on a 4x4 torus without touching
+    assert!(matches!(
+        generate_board_with_rules(4, &rules, &mut SeededRng::new(0)),
+        Err(GeneratorError::UnsupportedSize { size: 4 })
+    ));
+    assert!(matches!(
+        generate_board_with_rules(8, &RuleSet::star_battle(2), &mut SeededRng::new(0)),
+        Err(GeneratorError::UnsupportedRules)
+    ));
+}
@@ -0,0 +1,125 @@
+use std::cmp::Ordering;
+
+use super::{Heuristic, HeuristicContext};
+
+/// Sums the scores of several heuristics, each scaled by its weight.
+#[derive(Debug)]
+pub struct Weighted {
+    parts: Vec<(Box<dyn Heuristic>, f32)>,
+}
+
+/// Orders by the first heuristic and breaks its ties with the next ones.
+/// Scores are the dense rank of every placement, starting from 0.
+#[derive(Debug)]
+pub struct Lexicographic {
+    parts: Vec<Box<dyn Heuristic>>,
+}
+
+/// Combines heuristics into a weighted sum of their scores, see `Heuristic::weighted`.
+pub fn weighted<H: Heuristic + 'static>(parts: impl IntoIterator<Item = (H, f32)>) -> Weighted {
+    Weighted {
+        parts: parts
+            .into_iter()
+            .map(|(heuristic, weight)| (Box::new(heuristic) as Box<dyn Heuristic>, weight))
+            .collect(),
+    }
+}
+
+/// Combines heuristics into a tie-breaker chain, see `Heuristic::lexicographic`.
+pub fn lexicographic<H: Heuristic + 'static>(parts: impl IntoIterator<Item = H>) -> Lexicographic {
+    Lexicographic {
+        parts: parts
+            .into_iter()
+            .map(|heuristic| Box::new(heuristic) as Box<dyn Heuristic>)
+            .collect(),
+    }
+}
+
+impl Heuristic for Weighted {
+    fn score(&self, ctx: &HeuristicContext) -> Vec<((usize, usize), f32)> {
+        let mut totals = vec![0.0; ctx.positions.len()];
+
+        // a zero weight would turn unscored cells into `0 * INF = NaN`
+        for (heuristic, weight) in self.parts.iter().filter(|(_, weight)| *weight != 0.0) {
+            for (total, score) in totals
+                .iter_mut()
+                .zip(aligned_scores(heuristic.as_ref(), ctx))
+            {
+                *total += weight * score;
+            }
+        }
+
+        ctx.positions.iter().copied().zip(totals).collect()
+    }
+
+    fn reset(&self) {
+        self.parts
+            .iter()
+            .for_each(|(heuristic, _)| heuristic.reset());
+    }
+}
+
+impl Heuristic for Lexicographic {
+    fn score(&self, ctx: &HeuristicContext) -> Vec<((usize, usize), f32)> {
+        let keys: Vec<Vec<f32>> = self
+            .parts
+            .iter()
+            .map(|heuristic| {
+                // NaN goes last like in `TieBreak`, whatever its sign bit
+                aligned_scores(heuristic.as_ref(), ctx)
+                    .into_iter()
+                    .map(|score| if score.is_nan() { f32::INFINITY } else { score })
+                    .collect()
+            })
+            .collect();
+        let compare = |a: usize, b: usize| {
+            keys.iter()
+                .map(|key| key[a].total_cmp(&key[b]))
+                .find(|&ordering| ordering != Ordering::Equal)
+                .unwrap_or(Ordering::Equal)
+        };
+
+        let mut order: Vec<usize> = (0..ctx.positions.len()).collect();
+        order.sort_by(|&a, &b| compare(a, b));
+
+        let mut ranks = vec![0.0; order.len()];
+        let mut rank = 0;
+        for (i, &position) in order.iter().enumerate() {
+            if i > 0 && compare(order[i - 1], position) != Ordering::Equal {
+                rank += 1;
+            }
+            ranks[position] = rank as f32;
+        }
+
+        ctx.positions.iter().copied().zip(ranks).collect()
+    }
+
+    fn reset(&self) {
+        self.parts.iter().for_each(|heuristic| heuristic.reset());
+    }
+}
+
+impl<H: Heuristic + ?Sized> Heuristic for Box<H> {
+    #[inline]
+    fn score(&self, ctx: &HeuristicContext) -> Vec<((usize, usize), f32)> {
+        (**self).score(ctx)
+    }
+
+    fn reset(&self) {
+        (**self).reset();
+    }
+}
+
+/// Scores of `heuristic` in the order of `ctx.positions`. Positions it leaves
+/// out score `f32::INFINITY`, so they are explored last.
+pub(super) fn aligned_scores(heuristic: &dyn Heuristic, ctx: &HeuristicContext) -> Vec<f32> {
+    let mut by_cell = vec![f32::INFINITY; ctx.rows * ctx.cols];
+    for ((r, c), score) in heuristic.score(ctx) {
+        by_cell[r * ctx.cols + c] = score;
+    }
+
+    ctx.positions
+        .iter()
+        .map(|&(r, c)| by_cell[r * ctx.cols + c])
+        .collect()
+}
@@ -0,0 +1,90 @@
+use super::{Heuristic, HeuristicContext, eliminated};
+
+/// Weights of the placement features a `LinearHeuristic` sums.
+///
+/// With the `serde` feature the weights load from the JSON written by the
+/// benchmark's `tune` command.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinearWeights {
+    /// Empty cells left in the placement's region.
+    pub region_empty: f32,
+    /// Empty cells left in the placement's row.
+    pub row_empty: f32,
+    /// Empty cells left in the placement's column.
+    pub col_empty: f32,
+    /// Empty cells the placement shares a row, column or region with.
+    pub eliminated: f32,
+}
+
+impl LinearWeights {
+    pub const FEATURES: usize = 4;
+
+    pub fn to_array(self) -> [f32; Self::FEATURES] {
+        [
+            self.region_empty,
+            self.row_empty,
+            self.col_empty,
+            self.eliminated,
+        ]
+    }
+
+    pub fn from_array(
+        [region_empty, row_empty, col_empty, eliminated]: [f32; Self::FEATURES],
+    ) -> Self {
+        LinearWeights {
+            region_empty,
+            row_empty,
+            col_empty,
+            eliminated,
+        }
+    }
+}
+
+/// Matches `smallest_region_by_empty_cells`.
+impl Default for LinearWeights {
+    fn default() -> Self {
+        LinearWeights {
+            region_empty: 1.0,
+            row_empty: 0.0,
+            col_empty: 0.0,
+            eliminated: 0.0,
+        }
+    }
+}
+
+/// Scores a placement with a weighted sum of its features.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LinearHeuristic {
+    pub weights: LinearWeights,
+}
+
+impl LinearHeuristic {
+    pub fn new(weights: LinearWeights) -> Self {
+        LinearHeuristic { weights }
+    }
+}
+
+impl Heuristic for LinearHeuristic {
+    fn score(&self, ctx: &HeuristicContext) -> Vec<((usize, usize), f32)> {
+        let weights = self.weights.to_array();
+        ctx.positions
+            .iter()
+            .map(|&(r, c)| {
+                let color = ctx.region_at(r * ctx.cols + c);
+                let features = [
+                    ctx.empty_in_region(color),
+                    ctx.empty_in_row(r),
+                    ctx.empty_in_col(c),
+                    eliminated(ctx, r, c),
+                ];
+                let score = weights
+                    .iter()
+                    .zip(features)
+                    .map(|(weight, feature)| weight * feature as f32)
+                    .sum();
+                ((r, c), score)
+            })
+            .collect()
+    }
+}
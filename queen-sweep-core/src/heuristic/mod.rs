@@ -0,0 +1,356 @@
+mod combine;
+pub use combine::{Lexicographic, Weighted, lexicographic, weighted};
+
+mod linear;
+pub use linear::{LinearHeuristic, LinearWeights};
+
+mod registry;
+pub use registry::{HeuristicInfo, find, registry, with_tag};
+
+mod tie_break;
+pub use tie_break::TieBreak;
+pub(crate) use tie_break::sort_placements;
+
+use crate::{
+    CellState, GameState,
+    game_state::{ColorGrid, RegionIndex},
+};
+use queen_sweep_macros::heuristic;
+use std::{cell::OnceCell, sync::Arc};
+
+/// What a heuristic sees of the state it orders placements for.
+///
+/// Aggregates are computed on first use and cached for the node, so every
+/// heuristic of a composite shares them.
+pub struct HeuristicContext<'a> {
+    pub positions: &'a [(usize, usize)],
+    pub rows: usize,
+    pub cols: usize,
+    pub regions: usize,
+    pub states: &'a [CellState],
+    pub colors_with_queens: &'a [bool],
+    pub colors: &'a ColorGrid,
+    pub region_index: &'a RegionIndex,
+    state: &'a GameState,
+    counts: OnceCell<UnitCounts>,
+}
+
+/// Empty cells and queens of every row, column and region.
+struct UnitCounts {
+    row_empty: Vec<usize>,
+    col_empty: Vec<usize>,
+    region_empty: Vec<usize>,
+    row_queens: Vec<usize>,
+    col_queens: Vec<usize>,
+    region_queens: Vec<usize>,
+}
+pub type HeuristicFn = fn(&HeuristicContext) -> Vec<((usize, usize), f32)>;
+
+/// Scores candidate placements, lower scores are explored first.
+///
+/// Unlike a `HeuristicFn`, an implementor can carry parameters and caches.
+/// Caches need interior mutability, as the solver only holds a shared reference.
+pub trait Heuristic: Send + Sync {
+    fn score(&self, ctx: &HeuristicContext) -> Vec<((usize, usize), f32)>;
+
+    /// Called once before a search starts, e.g. to clear per-search caches.
+    fn reset(&self) {}
+
+    /// Combines heuristics into a weighted sum of their scores.
+    ///
+    /// ```
+    /// use queen_sweep_core::heuristic::{self, Heuristic, HeuristicFn};
+    ///
+    /// let combined = HeuristicFn::weighted([
+    ///     (heuristic::smallest_region_first as HeuristicFn, 0.7),
+    ///     (heuristic::smallest_region_by_empty_cells, 0.3),
+    /// ]);
+    /// ```
+    fn weighted(parts: impl IntoIterator<Item = (Self, f32)>) -> Weighted
+    where
+        Self: Sized + 'static,
+    {
+        combine::weighted(parts)
+    }
+
+    /// Combines heuristics into a tie-breaker chain, ordering by the first one.
+    fn lexicographic(parts: impl IntoIterator<Item = Self>) -> Lexicographic
+    where
+        Self: Sized + 'static,
+    {
+        combine::lexicographic(parts)
+    }
+}
+
+impl Heuristic for HeuristicFn {
+    #[inline]
+    fn score(&self, ctx: &HeuristicContext) -> Vec<((usize, usize), f32)> {
+        self(ctx)
+    }
+}
+
+/// Lets several states, or the caller, share one heuristic and its caches.
+impl<H: Heuristic + ?Sized> Heuristic for Arc<H> {
+    #[inline]
+    fn score(&self, ctx: &HeuristicContext) -> Vec<((usize, usize), f32)> {
+        (**self).score(ctx)
+    }
+
+    fn reset(&self) {
+        (**self).reset();
+    }
+}
+
+impl std::fmt::Debug for dyn Heuristic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("dyn Heuristic")
+    }
+}
+
+impl<'a> HeuristicContext<'a> {
+    pub fn new(state: &'a GameState, positions: &'a [(usize, usize)]) -> Self {
+        HeuristicContext {
+            positions,
+            rows: state.rows(),
+            cols: state.cols(),
+            regions: state.region_count(),
+            states: state.states(),
+            colors_with_queens: state.colors_with_queens(),
+            colors: state.colors(),
+            region_index: state.region_index(),
+            state,
+            counts: OnceCell::new(),
+        }
+    }
+
+    /// Region of a cell that is part of the board.
+    #[inline]
+    pub fn region_at(&self, idx: usize) -> usize {
+        self.colors.region(idx).expect("void cells are never empty")
+    }
+
+    /// Cells of a region, in row-major order.
+    #[inline]
+    pub fn region_cells(&self, color: usize) -> &'a [usize] {
+        self.region_index.cells(color)
+    }
+
+    #[inline]
+    pub fn empty_in_row(&self, r: usize) -> usize {
+        self.counts().row_empty[r]
+    }
+
+    #[inline]
+    pub fn empty_in_col(&self, c: usize) -> usize {
+        self.counts().col_empty[c]
+    }
+
+    #[inline]
+    pub fn empty_in_region(&self, color: usize) -> usize {
+        self.counts().region_empty[color]
+    }
+
+    #[inline]
+    pub fn queens_in_row(&self, r: usize) -> usize {
+        self.counts().row_queens[r]
+    }
+
+    #[inline]
+    pub fn queens_in_col(&self, c: usize) -> usize {
+        self.counts().col_queens[c]
+    }
+
+    #[inline]
+    pub fn queens_in_region(&self, color: usize) -> usize {
+        self.counts().region_queens[color]
+    }
+
+    /// Queens on the whole board.
+    pub fn queens_placed(&self) -> usize {
+        self.counts().row_queens.iter().sum()
+    }
+
+    /// Cells next to `idx` under the adjacency rule.
+    pub fn neighbors(&self, idx: usize) -> impl Iterator<Item = usize> + 'a {
+        self.state.neighbors(idx).iter().copied()
+    }
+
+    /// Cells a queen on `idx` rules out through adjacency and diagonals, not
+    /// counting its row, column and region. Cells can appear twice.
+    pub fn attacked_cells(&self, idx: usize) -> impl Iterator<Item = usize> + 'a {
+        self.state.attacked_cells(idx)
+    }
+
+    fn counts(&self) -> &UnitCounts {
+        self.counts.get_or_init(|| {
+            let mut counts = UnitCounts {
+                row_empty: vec![0; self.rows],
+                col_empty: vec![0; self.cols],
+                region_empty: vec![0; self.regions],
+                row_queens: vec![0; self.rows],
+                col_queens: vec![0; self.cols],
+                region_queens: vec![0; self.regions],
+            };
+
+            for (idx, state) in self.states.iter().enumerate() {
+                let (r, c) = (idx / self.cols, idx % self.cols);
+                match state {
+                    CellState::Empty => {
+                        counts.row_empty[r] += 1;
+                        counts.col_empty[c] += 1;
+                        counts.region_empty[self.region_at(idx)] += 1;
+                    }
+                    CellState::Queen => {
+                        counts.row_queens[r] += 1;
+                        counts.col_queens[c] += 1;
+                        counts.region_queens[self.region_at(idx)] += 1;
+                    }
+                    CellState::Blocked => {}
+                }
+            }
+
+            counts
+        })
+    }
+}
+
+/// Prioritizes smaller color regions.
+/// Counts all cells of a color regardless of cell state.
+#[heuristic(tags = ["region", "static"])]
+pub fn smallest_region_first(ctx: &HeuristicContext) -> Vec<((usize, usize), f32)> {
+    ctx.positions
+        .iter()
+        .map(|&(r, c)| {
+            let color = ctx.region_at(r * ctx.cols + c);
+            ((r, c), ctx.region_cells(color).len() as f32)
+        })
+        .collect()
+}
+
+/// Prioritizes regions with fewer empty cells.
+/// Counts only empty cells in each color region
+#[heuristic(tags = ["region"])]
+pub fn smallest_region_by_empty_cells(ctx: &HeuristicContext) -> Vec<((usize, usize), f32)> {
+    ctx.positions
+        .iter()
+        .map(|&(r, c)| {
+            let color = ctx.region_at(r * ctx.cols + c);
+            ((r, c), ctx.empty_in_region(color) as f32)
+        })
+        .collect()
+}
+
+/// Prioritizes rows with fewer empty cells.
+#[heuristic(tags = ["line"])]
+pub fn smallest_row_by_empty_cells(ctx: &HeuristicContext) -> Vec<((usize, usize), f32)> {
+    ctx.positions
+        .iter()
+        .map(|&(r, c)| ((r, c), ctx.empty_in_row(r) as f32))
+        .collect()
+}
+
+/// Prioritizes columns with fewer empty cells.
+#[heuristic(tags = ["line"])]
+pub fn smallest_col_by_empty_cells(ctx: &HeuristicContext) -> Vec<((usize, usize), f32)> {
+    ctx.positions
+        .iter()
+        .map(|&(r, c)| ((r, c), ctx.empty_in_col(c) as f32))
+        .collect()
+}
+
+/// Minimum remaining values over all units. A placement scores the empty
+/// cells left in the most constrained of its row, column and region, so the
+/// search branches on the unit with the fewest candidates.
+#[heuristic(tags = ["region", "line", "mrv"])]
+pub fn most_constrained_unit(ctx: &HeuristicContext) -> Vec<((usize, usize), f32)> {
+    ctx.positions
+        .iter()
+        .map(|&(r, c)| ((r, c), remaining_values(ctx, r, c) as f32))
+        .collect()
+}
+
+/// Like `most_constrained_unit`, but within a unit prefers placements that
+/// eliminate fewer candidates from their row, column and region, or by attack.
+#[heuristic(tags = ["region", "line", "mrv"])]
+pub fn most_constrained_unit_with_elimination(
+    ctx: &HeuristicContext,
+) -> Vec<((usize, usize), f32)> {
+    // keeps the elimination term below one, so it only breaks ties
+    let scale = (ctx.rows * ctx.cols) as f32;
+
+    ctx.positions
+        .iter()
+        .map(|&(r, c)| {
+            let score = remaining_values(ctx, r, c) as f32 + eliminated(ctx, r, c) as f32 / scale;
+            ((r, c), score)
+        })
+        .collect()
+}
+
+/// Empty cells left in the most constrained unit of a placement.
+fn remaining_values(ctx: &HeuristicContext, r: usize, c: usize) -> usize {
+    let color = ctx.region_at(r * ctx.cols + c);
+    ctx.empty_in_row(r)
+        .min(ctx.empty_in_col(c))
+        .min(ctx.empty_in_region(color))
+}
+
+/// Empty cells a placement shares its row, column or region with, or attacks.
+fn eliminated(ctx: &HeuristicContext, r: usize, c: usize) -> usize {
+    let idx = r * ctx.cols + c;
+    let color = ctx.region_at(idx);
+    let in_units =
+        |cell: usize| cell / ctx.cols == r || cell % ctx.cols == c || ctx.region_at(cell) == color;
+
+    // region cells in the row or column are counted there already
+    let shared = ctx
+        .region_cells(color)
+        .iter()
+        .filter(|&&cell| {
+            ctx.states[cell] == CellState::Empty && (cell / ctx.cols == r || cell % ctx.cols == c)
+        })
+        .count();
+
+    // attacked cells can repeat and can fall inside the units counted above
+    let mut attacked: Vec<usize> = ctx
+        .attacked_cells(idx)
+        .filter(|&cell| ctx.states[cell] == CellState::Empty && !in_units(cell))
+        .collect();
+    attacked.sort_unstable();
+    attacked.dedup();
+
+    // the placement itself is counted by all three units
+    ctx.empty_in_row(r) + ctx.empty_in_col(c) + ctx.empty_in_region(color) - shared - 2
+        + attacked.len()
+}
+
+/// Weighs the empty cells left in a placement's region against those left in
+/// its row. A `region_weight` of 1 matches `smallest_region_by_empty_cells`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RegionRowScarcity {
+    pub region_weight: f32,
+}
+
+impl Default for RegionRowScarcity {
+    fn default() -> Self {
+        RegionRowScarcity { region_weight: 0.5 }
+    }
+}
+
+impl Heuristic for RegionRowScarcity {
+    fn score(&self, ctx: &HeuristicContext) -> Vec<((usize, usize), f32)> {
+        let row_weight = 1.0 - self.region_weight;
+        ctx.positions
+            .iter()
+            .map(|&(r, c)| {
+                let color = ctx.region_at(r * ctx.cols + c);
+                let score = self.region_weight * ctx.empty_in_region(color) as f32
+                    + row_weight * ctx.empty_in_row(r) as f32;
+                ((r, c), score)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,40 @@
+use std::sync::OnceLock;
+
+use super::HeuristicFn;
+
+/// A heuristic function declared with `#[heuristic]`.
+#[derive(Debug, Clone, Copy)]
+pub struct HeuristicInfo {
+    /// The `name` argument, or else the kebab-case function name.
+    pub name: &'static str,
+    /// The `description` argument, or else the function's doc comment.
+    pub description: &'static str,
+    /// Free-form labels, e.g. the units a heuristic looks at.
+    pub tags: &'static [&'static str],
+    pub func: HeuristicFn,
+}
+
+inventory::collect!(HeuristicInfo);
+
+/// Every heuristic function declared with `#[heuristic]`, sorted by name.
+pub fn registry() -> &'static [&'static HeuristicInfo] {
+    static REGISTRY: OnceLock<Vec<&'static HeuristicInfo>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let mut infos: Vec<&'static HeuristicInfo> = inventory::iter::<HeuristicInfo>().collect();
+        infos.sort_unstable_by_key(|info| info.name);
+        infos
+    })
+}
+
+/// The registered heuristic called `name`.
+pub fn find(name: &str) -> Option<&'static HeuristicInfo> {
+    registry().iter().copied().find(|info| info.name == name)
+}
+
+/// Registered heuristics carrying `tag`.
+pub fn with_tag(tag: &str) -> impl Iterator<Item = &'static HeuristicInfo> + '_ {
+    registry()
+        .iter()
+        .copied()
+        .filter(move |info| info.tags.contains(&tag))
+}
@@ -0,0 +1,235 @@
+use std::sync::Arc;
+
+use crate::fixtures::puzzle_8x8_sol;
+use crate::{
+    GameState, depth_first_search,
+    heuristic::{
+        self, Heuristic, HeuristicContext, HeuristicFn, LinearHeuristic, LinearWeights, TieBreak,
+        most_constrained_unit, most_constrained_unit_with_elimination, smallest_col_by_empty_cells,
+        smallest_region_by_empty_cells, smallest_region_first, smallest_row_by_empty_cells,
+    },
+};
+
+/// Scores of `heuristic` on the empty board, keyed by position.
+fn scores(heuristic: &dyn Heuristic) -> Vec<((usize, usize), f32)> {
+    let state = GameState::try_from(puzzle_8x8_sol()).unwrap();
+    let positions: Vec<(usize, usize)> = (0..8).flat_map(|r| (0..8).map(move |c| (r, c))).collect();
+    let ctx = HeuristicContext::new(&state, &positions);
+
+    let mut scores = heuristic.score(&ctx);
+    scores.sort_by_key(|&(pos, _)| pos);
+    scores
+}
+
+#[test]
+fn test_context_aggregates() {
+    let state = GameState::try_from(puzzle_8x8_sol())
+        .unwrap()
+        .place_queen(1, 5);
+    let positions = state.valid_placements();
+    let ctx = HeuristicContext::new(&state, &positions);
+
+    assert_eq!(ctx.queens_placed(), 1);
+    assert_eq!((ctx.queens_in_row(1), ctx.queens_in_col(5)), (1, 1));
+    assert_eq!(ctx.queens_in_region(4), 1);
+    assert_eq!((ctx.empty_in_row(1), ctx.empty_in_col(5)), (0, 0));
+    assert_eq!(ctx.region_cells(4), &[13]);
+    assert_eq!(ctx.region_at(13), 4);
+
+    let empty_in_rows: usize = (0..8).map(|r| ctx.empty_in_row(r)).sum();
+    let empty_in_regions: usize = (0..ctx.regions)
+        .map(|color| ctx.empty_in_region(color))
+        .sum();
+    assert_eq!(empty_in_rows, positions.len());
+    assert_eq!(empty_in_regions, positions.len());
+
+    let mut neighbors: Vec<usize> = ctx.neighbors(0).collect();
+    neighbors.sort_unstable();
+    assert_eq!(neighbors, vec![1, 8, 9]);
+}
+
+#[test]
+fn test_registry() {
+    let info = heuristic::find("smallest-region-first").unwrap();
+    assert_eq!(
+        info.func as usize,
+        smallest_region_first as HeuristicFn as usize
+    );
+    assert_eq!(
+        info.description,
+        "Prioritizes smaller color regions. Counts all cells of a color regardless of cell state."
+    );
+    assert!(heuristic::find("largest-region-first").is_none());
+    assert_eq!(info.tags, ["region", "static"]);
+
+    let line: Vec<&str> = heuristic::with_tag("line").map(|info| info.name).collect();
+    assert!(line.contains(&"smallest-row-by-empty-cells"));
+    assert!(!line.contains(&"smallest-region-first"));
+
+    // every #[heuristic] function registers itself, sorted by unique names
+    let names: Vec<&str> = heuristic::registry().iter().map(|info| info.name).collect();
+    assert_eq!(names.len(), 6);
+    assert!(names.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn test_weighted_sums_scores() {
+    let size = scores(&(smallest_region_first as HeuristicFn));
+    let empty = scores(&(smallest_region_by_empty_cells as HeuristicFn));
+    let combined = scores(&HeuristicFn::weighted([
+        (smallest_region_first as HeuristicFn, 0.7),
+        (smallest_region_by_empty_cells, 0.3),
+    ]));
+
+    for ((size, empty), (pos, score)) in size.iter().zip(&empty).zip(combined) {
+        assert_eq!(pos, size.0);
+        assert!((score - (0.7 * size.1 + 0.3 * empty.1)).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn test_most_constrained_unit() {
+    let score_at =
+        |scores: &[((usize, usize), f32)], pos| scores.iter().find(|&&(p, _)| p == pos).unwrap().1;
+    let mrv = scores(&(most_constrained_unit as HeuristicFn));
+    let eliminating = scores(&(most_constrained_unit_with_elimination as HeuristicFn));
+
+    // region 4 is the single cell (1, 5)
+    assert_eq!(score_at(&mrv, (1, 5)), 1.0);
+    assert_eq!(score_at(&mrv, (0, 0)), 7.0);
+    assert_eq!(score_at(&mrv, (0, 5)), 8.0);
+    // 14 cells in its row and column, plus the 4 diagonal neighbors outside them
+    assert!((score_at(&eliminating, (1, 5)) - (1.0 + 18.0 / 64.0)).abs() < 1e-6);
+    assert!(
+        eliminating
+            .iter()
+            .all(|&(pos, score)| score.floor() == score_at(&mrv, pos))
+    );
+}
+
+#[test]
+fn test_line_heuristics_solve() {
+    let heuristics: [HeuristicFn; 4] = [
+        smallest_row_by_empty_cells,
+        smallest_col_by_empty_cells,
+        most_constrained_unit,
+        most_constrained_unit_with_elimination,
+    ];
+
+    for heuristic in heuristics {
+        let state = GameState::from_color_regions(puzzle_8x8_sol(), Some(heuristic)).unwrap();
+        assert!(depth_first_search(state).0.is_some());
+    }
+}
+
+#[test]
+fn test_linear_heuristic() {
+    let default = scores(&LinearHeuristic::default());
+    assert_eq!(
+        default,
+        scores(&(smallest_region_by_empty_cells as HeuristicFn))
+    );
+
+    let weights = LinearWeights::from_array([0.0, 1.0, 0.0, 0.0]);
+    assert_eq!(weights.to_array(), [0.0, 1.0, 0.0, 0.0]);
+    assert_eq!(
+        scores(&LinearHeuristic::new(weights)),
+        scores(&(smallest_row_by_empty_cells as HeuristicFn))
+    );
+}
+
+#[test]
+fn test_combinators_handle_unscored_and_nan() {
+    let first_only: HeuristicFn = |ctx| vec![(ctx.positions[0], 0.0)];
+    let combined = scores(&HeuristicFn::weighted([
+        (smallest_region_first as HeuristicFn, 1.0),
+        (first_only, 0.0),
+    ]));
+    assert!(combined.iter().all(|(_, score)| !score.is_nan()));
+
+    // a negative NaN ranks after every number
+    let neg_nan_first: HeuristicFn = |ctx| {
+        ctx.positions
+            .iter()
+            .enumerate()
+            .map(|(i, &pos)| (pos, if i == 0 { -f32::NAN } else { 0.0 }))
+            .collect()
+    };
+    let ranks = scores(&HeuristicFn::lexicographic([neg_nan_first]));
+    assert_eq!(ranks[0].1, 1.0);
+    assert!(ranks[1..].iter().all(|(_, rank)| *rank == 0.0));
+}
+
+#[test]
+fn test_lexicographic_breaks_ties() {
+    // by region size, then by row
+    let by_row: HeuristicFn = |ctx| {
+        ctx.positions
+            .iter()
+            .map(|&(r, c)| ((r, c), r as f32))
+            .collect()
+    };
+    let ranks = scores(&HeuristicFn::lexicographic([
+        smallest_region_first as HeuristicFn,
+        by_row,
+    ]));
+    let size = scores(&(smallest_region_first as HeuristicFn));
+
+    for (a, b) in ranks.iter().zip(&size) {
+        for (c, d) in ranks.iter().zip(&size) {
+            let expected = b.1.total_cmp(&d.1).then(a.0.0.cmp(&c.0.0));
+            assert_eq!(a.1.total_cmp(&c.1), expected);
+        }
+    }
+}
+
+fn constant(ctx: &HeuristicContext) -> Vec<((usize, usize), f32)> {
+    // reversed, so only the tie-break can restore row-major order
+    ctx.positions.iter().rev().map(|&pos| (pos, 1.0)).collect()
+}
+
+fn placements(tie_break: TieBreak, heuristic: HeuristicFn) -> Vec<(usize, usize)> {
+    GameState::try_from(puzzle_8x8_sol())
+        .unwrap()
+        .with_heuristic(heuristic)
+        .with_tie_break(tie_break)
+        .valid_placements()
+}
+
+#[test]
+fn test_tie_break_policies() {
+    let row_major: Vec<(usize, usize)> = (0..8).flat_map(|r| (0..8).map(move |c| (r, c))).collect();
+    assert_eq!(placements(TieBreak::RowMajor, constant), row_major);
+
+    let seeded = placements(TieBreak::Seeded(7), constant);
+    assert_ne!(seeded, row_major);
+    assert_eq!(seeded, placements(TieBreak::Seeded(7), constant));
+    // nearby seeds are not the same order with neighboring cells swapped
+    let pairs_swapped: Vec<(usize, usize)> = seeded.iter().map(|&(r, c)| (r, c ^ 1)).collect();
+    assert_ne!(placements(TieBreak::Seeded(6), constant), pairs_swapped);
+
+    // reverse row order through a secondary heuristic
+    let by_row: HeuristicFn = |ctx| {
+        ctx.positions
+            .iter()
+            .map(|&(r, c)| ((r, c), -(r as f32)))
+            .collect()
+    };
+    let secondary = placements(TieBreak::Heuristic(Arc::new(by_row)), constant);
+    assert_eq!(secondary.first(), Some(&(7, 0)));
+    assert_eq!(secondary.last(), Some(&(0, 7)));
+}
+
+#[test]
+fn test_nan_scores_go_last() {
+    let nan_first_row: HeuristicFn = |ctx| {
+        ctx.positions
+            .iter()
+            .map(|&(r, c)| ((r, c), if r == 0 { f32::NAN } else { c as f32 }))
+            .collect()
+    };
+
+    let order = placements(TieBreak::RowMajor, nan_first_row);
+    assert!(order[56..].iter().all(|&(r, _)| r == 0));
+    assert!(order[..56].windows(2).all(|pair| pair[0].1 <= pair[1].1));
+}
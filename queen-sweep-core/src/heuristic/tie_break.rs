@@ -0,0 +1,74 @@
+use std::{cmp::Ordering, sync::Arc};
+
+use super::{Heuristic, HeuristicContext, combine::aligned_scores};
+use crate::SeededRng;
+
+/// How placements with equal heuristic scores are ordered. NaN scores always
+/// go last, behind every valid score.
+#[derive(Debug, Clone, Default)]
+pub enum TieBreak {
+    /// Row by row, then column by column.
+    #[default]
+    RowMajor,
+    /// A fixed pseudo-random order derived from the seed.
+    Seeded(u64),
+    /// By the scores of a second heuristic, then row-major.
+    Heuristic(Arc<dyn Heuristic>),
+}
+
+/// Sorts `scored` by score, ordering ties by `tie_break`. The sort is stable
+/// and fully determined by its inputs.
+pub(crate) fn sort_placements(
+    scored: Vec<((usize, usize), f32)>,
+    ctx: &HeuristicContext,
+    tie_break: &TieBreak,
+) -> Vec<(usize, usize)> {
+    let cells = ctx.rows * ctx.cols;
+    let secondary = match tie_break {
+        TieBreak::RowMajor => Secondary::None,
+        TieBreak::Seeded(seed) => {
+            // one draw per cell, so a cell keeps its key whatever the candidates
+            let mut rng = SeededRng::new(*seed);
+            Secondary::Keys((0..cells).map(|_| rng.next_u64()).collect())
+        }
+        TieBreak::Heuristic(heuristic) => {
+            let mut by_cell = vec![f32::NAN; cells];
+            let scores = aligned_scores(heuristic.as_ref(), ctx);
+            for (&(r, c), score) in ctx.positions.iter().zip(scores) {
+                by_cell[r * ctx.cols + c] = score;
+            }
+            Secondary::Scores(by_cell)
+        }
+    };
+    let idx = |(r, c): (usize, usize)| r * ctx.cols + c;
+
+    let mut scored = scored;
+    scored.sort_by(|a, b| {
+        compare_scores(a.1, b.1)
+            .then_with(|| match &secondary {
+                Secondary::None => Ordering::Equal,
+                Secondary::Keys(keys) => keys[idx(a.0)].cmp(&keys[idx(b.0)]),
+                Secondary::Scores(scores) => compare_scores(scores[idx(a.0)], scores[idx(b.0)]),
+            })
+            .then(a.0.cmp(&b.0))
+    });
+
+    scored.into_iter().map(|(pos, _)| pos).collect()
+}
+
+/// Per-cell keys that order placements with equal scores.
+enum Secondary {
+    None,
+    Keys(Vec<u64>),
+    Scores(Vec<f32>),
+}
+
+/// Total order on scores with NaN after every number.
+fn compare_scores(a: f32, b: f32) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => a.partial_cmp(&b).expect("neither score is NaN"),
+    }
+}
@@ -1,10 +1,35 @@
+mod bitset;
 mod cell_state;
+mod clues;
 mod dfs;
+mod difficulty;
+mod editor;
+#[cfg(test)]
+mod fixtures;
 mod game_state;
+mod generator;
+mod logic;
+mod rules;
+mod symmetry;
 
 #[allow(dead_code)]
 pub mod heuristic;
 
 pub use cell_state::CellState;
-pub use dfs::depth_first_search;
-pub use game_state::{GameState, GameStateError};
+pub use clues::{Clue, ClueError, ClueKind, apply_clues, minimal_clues};
+pub use dfs::{
+    Branching, SolveOptions, SolveStats, TranspositionConfig, TranspositionPolicy, count_solutions,
+    depth_first_search, depth_first_search_with_options, enumerate_solutions, has_unique_solution,
+};
+pub use difficulty::{Difficulty, DifficultyReport, rate_difficulty};
+pub use editor::{EditError, EditOutcome, LayoutEditor};
+pub use game_state::{
+    ColorGrid, GameState, GameStateError, LookaheadDepth, RegionId, RegionIndex, VOID_COLOR,
+};
+pub use generator::{
+    GenerateOptions, GeneratedBoard, GeneratorError, SeededRng, generate_board,
+    generate_board_with_difficulty, generate_board_with_rules,
+};
+pub use logic::{Deduction, LogicalSolve, Technique, solve_logically};
+pub use rules::{Adjacency, RuleSet};
+pub use symmetry::{Symmetry, SymmetryGroup};
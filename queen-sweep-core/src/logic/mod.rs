@@ -0,0 +1,358 @@
+use std::collections::BTreeMap;
+
+use crate::{CellState, GameState, bitset::BitSet, game_state::ColorGrid};
+
+/// Deduction techniques of the logical solver, ordered from easiest to hardest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Technique {
+    /// A row, column or region has exactly one empty cell left.
+    SingleCell,
+    /// A region confined to a single line, or a line confined to a single region.
+    LineConfinement,
+    /// N regions confined to N lines, or N lines confined to N regions.
+    RegionLineSets,
+    /// A queen on the cell would leave another region without empty cells.
+    Contradiction,
+}
+
+impl Technique {
+    pub const ALL: [Technique; 4] = [
+        Technique::SingleCell,
+        Technique::LineConfinement,
+        Technique::RegionLineSets,
+        Technique::Contradiction,
+    ];
+
+    fn apply(self, state: &GameState, units: &Units) -> Option<Deduction> {
+        let cols = state.cols();
+
+        let (placed, blocked) = match self {
+            Technique::SingleCell => {
+                let idx = units.single_cell()?;
+                (Some(idx), Vec::new())
+            }
+            Technique::LineConfinement => (None, units.confinement(1..=1)?),
+            Technique::RegionLineSets => {
+                let max_set = units.open_regions() / 2;
+                (None, units.confinement(2..=max_set)?)
+            }
+            Technique::Contradiction => (None, state.cells_failing_lookahead()),
+        };
+
+        if placed.is_none() && blocked.is_empty() {
+            return None;
+        }
+
+        Some(Deduction {
+            technique: self,
+            placed: placed.map(|idx| (idx / cols, idx % cols)),
+            blocked: blocked
+                .into_iter()
+                .map(|idx| (idx / cols, idx % cols))
+                .collect(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deduction {
+    pub technique: Technique,
+    pub placed: Option<(usize, usize)>,
+    pub blocked: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogicalSolve {
+    pub state: GameState,
+    pub deductions: Vec<Deduction>,
+}
+
+impl LogicalSolve {
+    #[inline]
+    pub fn is_solved(&self) -> bool {
+        self.state.is_goal_state()
+    }
+
+    /// Number of times each technique was applied.
+    pub fn technique_counts(&self) -> BTreeMap<Technique, usize> {
+        let mut counts = BTreeMap::new();
+        for deduction in &self.deductions {
+            *counts.entry(deduction.technique).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    #[inline]
+    pub fn hardest_technique(&self) -> Option<Technique> {
+        self.deductions.iter().map(|d| d.technique).max()
+    }
+
+    /// Queens placed by deduction, in the order they were found.
+    pub fn forced_queens(&self) -> Vec<(usize, usize)> {
+        self.deductions.iter().filter_map(|d| d.placed).collect()
+    }
+}
+
+/// Applies deductions, always retrying the easiest technique first,
+/// until the board is solved or no technique makes progress.
+///
+/// The techniques assume a square board with one queen per row, column and
+/// region, so other boards are returned without deductions.
+pub fn solve_logically(game_state: &GameState) -> LogicalSolve {
+    let mut state = game_state.clone();
+    let mut deductions = Vec::new();
+
+    if !state.is_classic() {
+        return LogicalSolve { state, deductions };
+    }
+
+    while !state.is_goal_state() {
+        let units = Units::new(&state);
+        if units.has_dead_unit() {
+            break;
+        }
+
+        let Some(deduction) = Technique::ALL
+            .into_iter()
+            .find_map(|technique| technique.apply(&state, &units))
+        else {
+            break;
+        };
+
+        state = match deduction.placed {
+            Some((r, c)) => state.place_queen_without_lookahead(r, c),
+            None => state.block_cells(&deduction.blocked),
+        };
+        deductions.push(deduction);
+    }
+
+    LogicalSolve { state, deductions }
+}
+
+/// Empty cells of every row, column and region that still needs a queen.
+struct Units {
+    width: usize,
+    colors: ColorGrid,
+    rows: Vec<Vec<usize>>,
+    cols: Vec<Vec<usize>>,
+    regions: Vec<Vec<usize>>,
+    row_done: Vec<bool>,
+    col_done: Vec<bool>,
+    region_done: Vec<bool>,
+}
+
+impl Units {
+    fn new(state: &GameState) -> Self {
+        let (rows, width) = (state.rows(), state.cols());
+        let colors = state.colors().clone();
+
+        let mut units = Units {
+            width,
+            rows: vec![Vec::new(); rows],
+            cols: vec![Vec::new(); width],
+            regions: vec![Vec::new(); state.region_count()],
+            row_done: vec![false; rows],
+            col_done: vec![false; width],
+            region_done: state.colors_with_queens().to_vec(),
+            colors,
+        };
+
+        for (idx, cell) in state.states().iter().enumerate() {
+            let (r, c) = (idx / width, idx % width);
+            match cell {
+                CellState::Queen => {
+                    units.row_done[r] = true;
+                    units.col_done[c] = true;
+                }
+                CellState::Empty => {
+                    units.rows[r].push(idx);
+                    units.cols[c].push(idx);
+                    let region = units.region_of(idx);
+                    units.regions[region].push(idx);
+                }
+                CellState::Blocked => {}
+            }
+        }
+
+        units
+    }
+
+    #[inline]
+    fn region_of(&self, idx: usize) -> usize {
+        self.colors.region(idx).expect("void cells are never empty")
+    }
+
+    fn groups(&self) -> [(&[Vec<usize>], &[bool]); 3] {
+        [
+            (&self.rows, &self.row_done),
+            (&self.cols, &self.col_done),
+            (&self.regions, &self.region_done),
+        ]
+    }
+
+    fn has_dead_unit(&self) -> bool {
+        self.groups().iter().any(|(cells, done)| {
+            cells
+                .iter()
+                .zip(done.iter())
+                .any(|(cells, &done)| !done && cells.is_empty())
+        })
+    }
+
+    fn open_regions(&self) -> usize {
+        self.region_done.iter().filter(|&&done| !done).count()
+    }
+
+    fn single_cell(&self) -> Option<usize> {
+        self.groups().into_iter().find_map(|(cells, done)| {
+            cells
+                .iter()
+                .zip(done.iter())
+                .find(|(cells, done)| !**done && cells.len() == 1)
+                .map(|(cells, _)| cells[0])
+        })
+    }
+
+    /// Looks for `k` units of one kind whose empty cells lie in exactly `k`
+    /// units of another kind, and returns the cells that can be blocked.
+    fn confinement(&self, sizes: std::ops::RangeInclusive<usize>) -> Option<Vec<usize>> {
+        let width = self.width;
+        let row_of = |idx: usize| idx / width;
+        let col_of = |idx: usize| idx % width;
+        let region_of = |idx: usize| self.region_of(idx);
+
+        for k in sizes {
+            let found = self
+                .confined_cells(
+                    &self.regions,
+                    &self.region_done,
+                    region_of,
+                    &self.rows,
+                    row_of,
+                    k,
+                )
+                .or_else(|| {
+                    self.confined_cells(
+                        &self.regions,
+                        &self.region_done,
+                        region_of,
+                        &self.cols,
+                        col_of,
+                        k,
+                    )
+                })
+                .or_else(|| {
+                    self.confined_cells(
+                        &self.rows,
+                        &self.row_done,
+                        row_of,
+                        &self.regions,
+                        region_of,
+                        k,
+                    )
+                })
+                .or_else(|| {
+                    self.confined_cells(
+                        &self.cols,
+                        &self.col_done,
+                        col_of,
+                        &self.regions,
+                        region_of,
+                        k,
+                    )
+                });
+
+            if found.is_some() {
+                return found;
+            }
+        }
+
+        None
+    }
+
+    fn confined_cells(
+        &self,
+        sources: &[Vec<usize>],
+        source_done: &[bool],
+        source_of: impl Fn(usize) -> usize,
+        targets: &[Vec<usize>],
+        target_of: impl Fn(usize) -> usize,
+        k: usize,
+    ) -> Option<Vec<usize>> {
+        let open: Vec<usize> = (0..sources.len()).filter(|&s| !source_done[s]).collect();
+        let target_sets: Vec<BitSet> = open
+            .iter()
+            .map(|&s| {
+                let mut set = BitSet::new(targets.len());
+                for &idx in &sources[s] {
+                    set.insert(target_of(idx));
+                }
+                set
+            })
+            .collect();
+
+        let mut visit = |chosen: &[usize], union: &BitSet| {
+            let mut members = BitSet::new(sources.len());
+            for &i in chosen {
+                members.insert(open[i]);
+            }
+
+            let blocked: Vec<usize> = (0..targets.len())
+                .filter(|&t| union.contains(t))
+                .flat_map(|t| targets[t].iter().copied())
+                .filter(|&idx| !members.contains(source_of(idx)))
+                .collect();
+
+            (!blocked.is_empty()).then_some(blocked)
+        };
+
+        find_confined_subset(
+            &target_sets,
+            k,
+            0,
+            &mut Vec::with_capacity(k),
+            &BitSet::new(targets.len()),
+            &mut visit,
+        )
+    }
+}
+
+fn find_confined_subset(
+    target_sets: &[BitSet],
+    k: usize,
+    start: usize,
+    chosen: &mut Vec<usize>,
+    union: &BitSet,
+    visit: &mut impl FnMut(&[usize], &BitSet) -> Option<Vec<usize>>,
+) -> Option<Vec<usize>> {
+    if chosen.len() == k {
+        return if union.len() == k {
+            visit(chosen, union)
+        } else {
+            None
+        };
+    }
+
+    for (i, targets) in target_sets.iter().enumerate().skip(start) {
+        let mut next = union.clone();
+        next.union_with(targets);
+
+        // unions only grow, so this branch can never shrink back to `k`
+        if next.len() > k {
+            continue;
+        }
+
+        chosen.push(i);
+        let found = find_confined_subset(target_sets, k, i + 1, chosen, &next, visit);
+        chosen.pop();
+
+        if found.is_some() {
+            return found;
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test;
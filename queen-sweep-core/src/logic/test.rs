@@ -0,0 +1,51 @@
+use crate::fixtures::{puzzle_3x3_nosol, puzzle_8x8_sol, puzzle_11x11_sol};
+use crate::{GameState, Technique, depth_first_search, heuristic, solve_logically};
+
+#[test]
+fn test_solve_logically_8x8() {
+    let state = GameState::from_color_regions(puzzle_8x8_sol(), None).unwrap();
+    let logical = solve_logically(&state);
+
+    assert!(logical.is_solved());
+    assert!(logical.hardest_technique() <= Some(Technique::LineConfinement));
+
+    let (solution, _) = depth_first_search(state);
+    assert_eq!(Some(logical.state), solution);
+}
+
+#[test]
+fn test_deductions_agree_with_solution() {
+    let state = GameState::from_color_regions(
+        puzzle_11x11_sol(),
+        Some(heuristic::smallest_region_by_empty_cells),
+    )
+    .unwrap();
+    let logical = solve_logically(&state);
+    let solution: Vec<(usize, usize)> = depth_first_search(state)
+        .0
+        .unwrap()
+        .queen_positions()
+        .collect();
+
+    assert!(logical.is_solved());
+    assert!(logical.technique_counts()[&Technique::RegionLineSets] > 0);
+
+    for deduction in &logical.deductions {
+        if let Some(pos) = deduction.placed {
+            assert!(solution.contains(&pos));
+        }
+        for pos in &deduction.blocked {
+            assert!(!solution.contains(pos), "{deduction:?}");
+        }
+    }
+
+    assert_eq!(logical.forced_queens().len(), solution.len());
+}
+
+#[test]
+fn test_solve_logically_stalls_without_solution() {
+    let state = GameState::from_color_regions(puzzle_3x3_nosol(), None).unwrap();
+    let logical = solve_logically(&state);
+
+    assert!(!logical.is_solved());
+}
@@ -3,22 +3,45 @@ use std::time::{Duration, Instant};
 use queen_sweep_core::{GameState, GameStateError, depth_first_search, heuristic::*};
 
 fn main() {
-    if let Err(e) = run() {
+    // an optional argument picks a registered heuristic by name
+    let heuristic_fn: HeuristicFn = match std::env::args().nth(1).as_deref() {
+        None => smallest_region_by_empty_cells,
+        Some("--list") => {
+            print_heuristics();
+            return;
+        }
+        Some(name) => match find(name) {
+            Some(info) => info.func,
+            None => {
+                eprintln!("Unknown heuristic: {}\n", name);
+                print_heuristics();
+                std::process::exit(1);
+            }
+        },
+    };
+
+    if let Err(e) = run(heuristic_fn) {
         eprintln!("Error: {}", e);
         std::process::exit(1);
     }
 }
 
-fn initialize_state() -> Result<GameState, GameStateError> {
+fn initialize_state(heuristic_fn: HeuristicFn) -> Result<GameState, GameStateError> {
     let color_regions = puzzle_11x11();
 
-    let heuristic_fn: Option<HeuristicFn> = Some(smallest_region_by_empty_cells);
+    GameState::from_color_regions(color_regions, Some(heuristic_fn))
+}
 
-    GameState::from_color_regions(color_regions, heuristic_fn)
+fn print_heuristics() {
+    println!("Heuristics:");
+    for info in registry() {
+        println!("  {:<40} [{}]", info.name, info.tags.join(", "));
+        println!("      {}", info.description);
+    }
 }
 
-fn run() -> Result<(), GameStateError> {
-    let state = initialize_state()?;
+fn run(heuristic_fn: HeuristicFn) -> Result<(), GameStateError> {
+    let state = initialize_state(heuristic_fn)?;
 
     print_board(&state, "Initial state");
 
@@ -0,0 +1,119 @@
+const KING_DISPLACEMENTS: [(i32, i32); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+const ORTHOGONAL_DISPLACEMENTS: [(i32, i32); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Neighbors of a queen that must stay free of other queens.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Adjacency {
+    /// No queen in any of the eight surrounding cells.
+    #[default]
+    King,
+    /// No queen directly above, below, left or right.
+    Orthogonal,
+    /// Queens may touch.
+    None,
+}
+
+impl Adjacency {
+    #[inline]
+    pub fn displacements(self) -> &'static [(i32, i32)] {
+        match self {
+            Adjacency::King => &KING_DISPLACEMENTS,
+            Adjacency::Orthogonal => &ORTHOGONAL_DISPLACEMENTS,
+            Adjacency::None => &[],
+        }
+    }
+}
+
+/// Placement rules of a puzzle variant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleSet {
+    /// Queens required in every row, column and region.
+    /// Classic Queens uses 1, k-star Star Battle uses k.
+    pub queens_per_unit: usize,
+    /// Queens required in each row, overriding `queens_per_unit` for rows.
+    pub row_queens: Option<Vec<usize>>,
+    /// Queens required in each column, overriding `queens_per_unit` for columns.
+    pub col_queens: Option<Vec<usize>>,
+    pub adjacency: Adjacency,
+    /// Forbid two queens on the same diagonal, as in classic N-queens.
+    pub forbid_diagonals: bool,
+    /// Wrap adjacency and diagonals around the board edges.
+    pub toroidal: bool,
+    /// Treat cells colored `VOID_COLOR` as holes in the board rather than a region.
+    pub void_cells: bool,
+}
+
+impl RuleSet {
+    pub fn star_battle(stars: usize) -> Self {
+        RuleSet {
+            queens_per_unit: stars,
+            ..RuleSet::default()
+        }
+    }
+
+    /// Classic N-queens on top of the regions: queens may touch, but never share a diagonal.
+    pub fn n_queens() -> Self {
+        RuleSet {
+            adjacency: Adjacency::None,
+            forbid_diagonals: true,
+            ..RuleSet::default()
+        }
+    }
+
+    #[inline]
+    pub fn row_target(&self, r: usize) -> usize {
+        self.row_queens
+            .as_ref()
+            .map_or(self.queens_per_unit, |queens| queens[r])
+    }
+
+    #[inline]
+    pub fn col_target(&self, c: usize) -> usize {
+        self.col_queens
+            .as_ref()
+            .map_or(self.queens_per_unit, |queens| queens[c])
+    }
+
+    /// Whether every row, column and region takes exactly one queen.
+    pub(crate) fn is_single_queen(&self) -> bool {
+        let single = |queens: &Option<Vec<usize>>| {
+            queens
+                .as_ref()
+                .is_none_or(|queens| queens.iter().all(|&q| q == 1))
+        };
+        self.queens_per_unit == 1 && single(&self.row_queens) && single(&self.col_queens)
+    }
+
+    /// Fewest cells a line needs to hold `queens` queens.
+    pub(crate) fn min_line_len(&self, queens: usize) -> usize {
+        match self.adjacency {
+            Adjacency::None => queens,
+            // a wrapped line also keeps its first and last queen apart
+            Adjacency::King | Adjacency::Orthogonal if self.toroidal && queens > 1 => 2 * queens,
+            Adjacency::King | Adjacency::Orthogonal => (2 * queens).saturating_sub(1),
+        }
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        RuleSet {
+            queens_per_unit: 1,
+            row_queens: None,
+            col_queens: None,
+            adjacency: Adjacency::default(),
+            forbid_diagonals: false,
+            toroidal: false,
+            void_cells: false,
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use crate::{
+    CellState,
+    game_state::{ColorGrid, compute_hash},
+};
+
+/// One of the eight transforms of the square (dihedral group D4).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+    Transpose,
+    AntiTranspose,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipHorizontal,
+        Symmetry::FlipVertical,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose,
+    ];
+
+    /// Maps `(r, c)` on a `size x size` board to its transformed position.
+    #[inline]
+    pub fn apply(self, r: usize, c: usize, size: usize) -> (usize, usize) {
+        let m = size - 1;
+        match self {
+            Symmetry::Identity => (r, c),
+            Symmetry::Rotate90 => (c, m - r),
+            Symmetry::Rotate180 => (m - r, m - c),
+            Symmetry::Rotate270 => (m - c, r),
+            Symmetry::FlipHorizontal => (r, m - c),
+            Symmetry::FlipVertical => (m - r, c),
+            Symmetry::Transpose => (c, r),
+            Symmetry::AntiTranspose => (m - c, m - r),
+        }
+    }
+}
+
+/// The transforms under which a board's region layout is invariant,
+/// up to a relabelling of the regions.
+#[derive(Debug, Clone)]
+pub struct SymmetryGroup {
+    symmetries: Vec<Symmetry>,
+    // cell index permutation for every non-identity symmetry
+    permutations: Vec<Box<[usize]>>,
+}
+
+impl SymmetryGroup {
+    /// The group containing only the identity.
+    pub fn trivial() -> Self {
+        SymmetryGroup {
+            symmetries: vec![Symmetry::Identity],
+            permutations: Vec::new(),
+        }
+    }
+
+    pub fn detect(size: usize, colors: &ColorGrid) -> Self {
+        let mut symmetries = vec![Symmetry::Identity];
+        let mut permutations = Vec::new();
+
+        for symmetry in Symmetry::ALL.into_iter().skip(1) {
+            let permutation: Box<[usize]> = (0..size * size)
+                .map(|idx| {
+                    let (r, c) = symmetry.apply(idx / size, idx % size, size);
+                    r * size + c
+                })
+                .collect();
+
+            if preserves_regions(colors, &permutation) {
+                symmetries.push(symmetry);
+                permutations.push(permutation);
+            }
+        }
+
+        SymmetryGroup {
+            symmetries,
+            permutations,
+        }
+    }
+
+    #[inline]
+    pub fn symmetries(&self) -> &[Symmetry] {
+        &self.symmetries
+    }
+
+    #[inline]
+    pub fn order(&self) -> usize {
+        self.symmetries.len()
+    }
+
+    #[inline]
+    pub fn is_trivial(&self) -> bool {
+        self.permutations.is_empty()
+    }
+
+    /// Returns the lexicographically smallest image of `states` under the group.
+    pub fn canonicalize(&self, states: &[CellState]) -> Vec<CellState> {
+        let mut best = states.to_vec();
+
+        for permutation in &self.permutations {
+            let image = permutation.iter().map(|&idx| states[idx]);
+            if image.clone().lt(best.iter().copied()) {
+                best.clear();
+                best.extend(image);
+            }
+        }
+
+        best
+    }
+
+    /// Hash shared by every state in the symmetry class of `states`.
+    #[inline]
+    pub fn canonical_hash(&self, states: &[CellState]) -> u64 {
+        compute_hash(&self.canonicalize(states))
+    }
+}
+
+fn preserves_regions(colors: &ColorGrid, permutation: &[usize]) -> bool {
+    // the transform must map every region onto exactly one region, and voids onto voids
+    let mut forward: HashMap<Option<usize>, Option<usize>> = HashMap::new();
+    let mut backward: HashMap<Option<usize>, Option<usize>> = HashMap::new();
+
+    for (idx, &target) in permutation.iter().enumerate() {
+        let from = colors.region(idx);
+        let to = colors.region(target);
+
+        if from.is_none() != to.is_none() {
+            return false;
+        }
+
+        if *forward.entry(from).or_insert(to) != to || *backward.entry(to).or_insert(from) != from {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,94 @@
+use crate::fixtures::{puzzle_8x8_sol, row_regions};
+use crate::{
+    ColorGrid, GameState, SolveOptions, Symmetry, SymmetryGroup, count_solutions,
+    depth_first_search_with_options, enumerate_solutions,
+};
+
+#[test]
+fn test_symmetry_apply_is_permutation() {
+    let size = 5;
+    for symmetry in Symmetry::ALL {
+        let mut images: Vec<(usize, usize)> = (0..size * size)
+            .map(|idx| symmetry.apply(idx / size, idx % size, size))
+            .collect();
+        images.sort_unstable();
+        images.dedup();
+        assert_eq!(images.len(), size * size, "{symmetry:?}");
+    }
+}
+
+#[test]
+fn test_detect_symmetry_group() {
+    let state = GameState::from_color_regions(puzzle_8x8_sol(), None).unwrap();
+    assert!(state.symmetry_group().is_trivial());
+
+    // rows are relabelled by vertical flips, but never mapped onto columns
+    let state = GameState::from_color_regions(row_regions(6), None).unwrap();
+    let group = state.symmetry_group();
+    assert_eq!(
+        group.symmetries(),
+        &[
+            Symmetry::Identity,
+            Symmetry::Rotate180,
+            Symmetry::FlipHorizontal,
+            Symmetry::FlipVertical,
+        ]
+    );
+
+    let uniform = ColorGrid::new(&[Some(0); 16], 1);
+    assert_eq!(SymmetryGroup::detect(4, &uniform).order(), 8);
+}
+
+#[test]
+fn test_canonical_hash_matches_symmetric_states() {
+    let state = GameState::from_color_regions(row_regions(6), None).unwrap();
+    let group = state.symmetry_group();
+
+    let left = state.place_queen(0, 1);
+    let mirrored = state.place_queen(0, 4);
+    let other = state.place_queen(0, 2);
+
+    assert_ne!(left.hash(), mirrored.hash());
+    assert_eq!(
+        group.canonical_hash(left.states()),
+        group.canonical_hash(mirrored.states())
+    );
+    assert_ne!(
+        group.canonical_hash(left.states()),
+        group.canonical_hash(other.states())
+    );
+}
+
+#[test]
+fn test_symmetry_pruning_explores_fewer_states() {
+    let state = GameState::from_color_regions(row_regions(6), None).unwrap();
+
+    let pruned = SolveOptions::default();
+    let unpruned = SolveOptions {
+        symmetry_pruning: false,
+        ..SolveOptions::default()
+    };
+
+    let (solution, _) = depth_first_search_with_options(state.clone(), &pruned);
+    assert!(solution.is_some_and(|s| s.is_goal_state()));
+
+    // exhausting the tree shows how much of it the symmetric images cover
+    let (pruned_solutions, pruned_stats) = enumerate_solutions(state.clone(), &pruned, None);
+    let (all_solutions, unpruned_stats) = enumerate_solutions(state, &unpruned, None);
+
+    assert!(pruned_stats.states_visited < unpruned_stats.states_visited);
+    assert!(pruned_solutions.len() < all_solutions.len());
+    assert_eq!(all_solutions.len(), 90);
+}
+
+#[test]
+fn test_count_solutions_ignores_symmetry() {
+    // one queen per row with no two queens touching: 2, 14, 90 (OEIS A002464)
+    for (size, expected) in [(4, 2), (5, 14), (6, 90)] {
+        let state = GameState::from_color_regions(row_regions(size), None).unwrap();
+        assert_eq!(count_solutions(state, None), expected);
+    }
+
+    let state = GameState::from_color_regions(row_regions(6), None).unwrap();
+    assert_eq!(count_solutions(state, Some(2)), 2);
+}
@@ -1,16 +1,369 @@
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{ItemFn, parse_macro_input};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{ToTokens, format_ident, quote};
+use syn::{
+    Error, Expr, FnArg, GenericArgument, GenericParam, Ident, ItemFn, Lit, LitStr, Meta,
+    PathArguments, ReturnType, Signature, Token, Type, bracketed, parse::Parser, parse_macro_input,
+    punctuated::Punctuated, spanned::Spanned,
+};
 
+const RETURN_TYPE: &str = "Vec<((usize, usize), f32)>";
+
+/// Checks that a function is a `HeuristicFn` and emits a `HeuristicInfo`
+/// constant for it, named after the function with an `_INFO` suffix. The
+/// constant is submitted to the registry through `inventory`, which must be a
+/// dependency of the calling crate.
+///
+/// Accepts `name = "..."`, `description = "..."` and `tags = ["..."]`. The
+/// name defaults to the function name in kebab case and the description to
+/// its doc comment.
 #[proc_macro_attribute]
-pub fn heuristic(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn heuristic(attr: TokenStream, item: TokenStream) -> TokenStream {
     let func = parse_macro_input!(item as ItemFn);
+
+    match expand(attr.into(), &func) {
+        Ok(expanded) => expanded.into(),
+        // keep the function so its callers do not report errors as well
+        Err(err) => {
+            let err = err.to_compile_error();
+            quote! { #func #err }.into()
+        }
+    }
+}
+
+/// Metadata given in the attribute.
+#[derive(Default)]
+struct HeuristicArgs {
+    name: Option<LitStr>,
+    description: Option<LitStr>,
+    tags: Option<Vec<LitStr>>,
+}
+
+fn expand(attr: TokenStream2, func: &ItemFn) -> syn::Result<TokenStream2> {
+    let args = parse_args(attr)?;
+    check_signature(&func.sig)?;
+
     let func_name = &func.sig.ident;
+    let vis = &func.vis;
+    let info_name = format_ident!("{}_INFO", func_name.to_string().to_uppercase());
 
-    let expanded = quote! {
+    let name = match args.name {
+        Some(name) => name.value(),
+        None => func_name.to_string().replace('_', "-"),
+    };
+    let description = match args.description {
+        Some(description) => description.value(),
+        None => doc_comment(func),
+    };
+    let tags = args.tags.unwrap_or_default();
+
+    // return types that may be aliases are left to the compiler, so point its
+    // type errors at the signature rather than the attribute
+    let mut func_ptr = func_name.clone();
+    func_ptr.set_span(match &func.sig.output {
+        ReturnType::Type(_, ty) => ty.span(),
+        ReturnType::Default => func.sig.span(),
+    });
+
+    Ok(quote! {
         #func
-        const _: HeuristicFn = #func_name;
+
+        #[doc = concat!("Registry entry of [`", stringify!(#func_name), "`].")]
+        #vis const #info_name: HeuristicInfo = HeuristicInfo {
+            name: #name,
+            description: #description,
+            tags: &[#(#tags),*],
+            func: #func_ptr,
+        };
+
+        inventory::submit! { #info_name }
+    })
+}
+
+fn parse_args(attr: TokenStream2) -> syn::Result<HeuristicArgs> {
+    let mut args = HeuristicArgs::default();
+
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("name") {
+            let name: LitStr = meta.value()?.parse()?;
+            let valid = !name.value().is_empty()
+                && name
+                    .value()
+                    .chars()
+                    .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-');
+            if !valid {
+                return Err(Error::new(
+                    name.span(),
+                    "heuristic names are kebab-case, e.g. `smallest-region-first`",
+                ));
+            }
+            set_once(&mut args.name, name, &meta.path)
+        } else if meta.path.is_ident("description") {
+            let description: LitStr = meta.value()?.parse()?;
+            set_once(&mut args.description, description, &meta.path)
+        } else if meta.path.is_ident("tags") {
+            if args.tags.is_some() {
+                return Err(meta.error("duplicate `tags` argument"));
+            }
+            let value = meta.value()?;
+            let content;
+            bracketed!(content in value);
+            args.tags = Some(
+                Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?
+                    .into_iter()
+                    .collect(),
+            );
+            Ok(())
+        } else {
+            Err(meta.error("unknown heuristic argument, expected `name`, `description` or `tags`"))
+        }
+    });
+    parser.parse2(attr)?;
+
+    Ok(args)
+}
+
+fn set_once(slot: &mut Option<LitStr>, value: LitStr, path: &syn::Path) -> syn::Result<()> {
+    if slot.is_some() {
+        let name = path.to_token_stream().to_string();
+        return Err(Error::new(
+            path.span(),
+            format!("duplicate `{name}` argument"),
+        ));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+/// Checks the signature against `HeuristicFn`, pointing errors at the part
+/// that does not match.
+fn check_signature(sig: &Signature) -> syn::Result<()> {
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(Error::new(asyncness.span(), "heuristics cannot be async"));
+    }
+    if let Some(param) = sig
+        .generics
+        .params
+        .iter()
+        .find(|param| !matches!(param, GenericParam::Lifetime(_)))
+    {
+        return Err(Error::new(param.span(), "heuristics cannot be generic"));
+    }
+
+    let mut inputs = sig.inputs.iter();
+    let Some(input) = inputs.next() else {
+        return Err(Error::new(
+            sig.paren_token.span.join(),
+            "expected a single parameter of type `&HeuristicContext`",
+        ));
+    };
+    if let Some(extra) = inputs.next() {
+        return Err(Error::new(
+            extra.span(),
+            "heuristics take a single `&HeuristicContext` parameter",
+        ));
+    }
+
+    let ty = match input {
+        FnArg::Receiver(receiver) => {
+            return Err(Error::new(
+                receiver.span(),
+                "expected `ctx: &HeuristicContext`, found `self`",
+            ));
+        }
+        FnArg::Typed(pat) => &pat.ty,
+    };
+    check_context_type(ty)?;
+
+    match &sig.output {
+        ReturnType::Default => Err(Error::new(
+            sig.paren_token.span.close(),
+            format!("expected return type `{RETURN_TYPE}`"),
+        )),
+        ReturnType::Type(_, ty) => {
+            let expected: Type = syn::parse_str(RETURN_TYPE)?;
+            match compare_types(ty, &expected) {
+                Shape::Differs => Err(Error::new(
+                    ty.span(),
+                    format!("expected `{RETURN_TYPE}`, found `{}`", pretty(ty)),
+                )),
+                Shape::Matches | Shape::Unknown => Ok(()),
+            }
+        }
+    }
+}
+
+/// How a written type relates to the expected one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    Matches,
+    Differs,
+    /// Involves a path that may be a type alias.
+    Unknown,
+}
+
+impl Shape {
+    fn and(self, other: Shape) -> Shape {
+        match (self, other) {
+            (Shape::Differs, _) | (_, Shape::Differs) => Shape::Differs,
+            (Shape::Unknown, _) | (_, Shape::Unknown) => Shape::Unknown,
+            (Shape::Matches, Shape::Matches) => Shape::Matches,
+        }
+    }
+}
+
+/// Compares `found` to `expected` by structure, so paths like `std::vec::Vec`
+/// match and only primitives are known to differ from other types.
+fn compare_types(found: &Type, expected: &Type) -> Shape {
+    match (unwrap_type(found), unwrap_type(expected)) {
+        (Type::Tuple(found), Type::Tuple(expected)) => {
+            if found.elems.len() != expected.elems.len() {
+                return Shape::Differs;
+            }
+            found
+                .elems
+                .iter()
+                .zip(&expected.elems)
+                .fold(Shape::Matches, |shape, (f, e)| {
+                    shape.and(compare_types(f, e))
+                })
+        }
+        (Type::Path(found), expected) => {
+            let Some(segment) = found.path.segments.last() else {
+                return Shape::Unknown;
+            };
+            let expected_segment = match expected {
+                Type::Path(expected) => expected.path.segments.last(),
+                _ => None,
+            };
+
+            match expected_segment {
+                Some(expected) if found.qself.is_none() && segment.ident == expected.ident => {
+                    compare_args(&segment.arguments, &expected.arguments)
+                }
+                _ if found.path.segments.len() == 1 && is_primitive(&segment.ident) => {
+                    Shape::Differs
+                }
+                _ => Shape::Unknown,
+            }
+        }
+        _ => Shape::Differs,
+    }
+}
+
+fn compare_args(found: &PathArguments, expected: &PathArguments) -> Shape {
+    match (found, expected) {
+        (PathArguments::None, PathArguments::None) => Shape::Matches,
+        (PathArguments::AngleBracketed(found), PathArguments::AngleBracketed(expected))
+            if found.args.len() == expected.args.len() =>
+        {
+            found
+                .args
+                .iter()
+                .zip(&expected.args)
+                .fold(Shape::Matches, |shape, args| {
+                    shape.and(match args {
+                        (GenericArgument::Type(f), GenericArgument::Type(e)) => compare_types(f, e),
+                        _ => Shape::Differs,
+                    })
+                })
+        }
+        _ => Shape::Differs,
+    }
+}
+
+fn unwrap_type(ty: &Type) -> &Type {
+    match ty {
+        Type::Paren(paren) => unwrap_type(&paren.elem),
+        Type::Group(group) => unwrap_type(&group.elem),
+        _ => ty,
+    }
+}
+
+fn is_primitive(ident: &Ident) -> bool {
+    const PRIMITIVES: [&str; 17] = [
+        "bool", "char", "str", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32",
+        "u64", "u128", "usize", "f32", "f64",
+    ];
+    PRIMITIVES.iter().any(|primitive| ident == primitive)
+}
+
+fn check_context_type(ty: &Type) -> syn::Result<()> {
+    let found = || {
+        Error::new(
+            ty.span(),
+            format!("expected `&HeuristicContext`, found `{}`", pretty(ty)),
+        )
     };
 
-    expanded.into()
+    let Type::Reference(reference) = ty else {
+        return Err(found());
+    };
+    if let Some(mutability) = &reference.mutability {
+        return Err(Error::new(
+            mutability.span(),
+            "the context is shared, expected `&HeuristicContext`",
+        ));
+    }
+    match reference.elem.as_ref() {
+        Type::Path(path)
+            if path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "HeuristicContext") =>
+        {
+            Ok(())
+        }
+        _ => Err(found()),
+    }
+}
+
+/// A type as it would be written, without the spaces `to_string` puts
+/// between all tokens.
+fn pretty(ty: &Type) -> String {
+    let tokens = ty.to_token_stream().to_string();
+    let chars: Vec<char> = tokens.chars().collect();
+    let mut out = String::with_capacity(tokens.len());
+
+    for (i, &ch) in chars.iter().enumerate() {
+        if ch == ' ' {
+            let prev = out.chars().last().unwrap_or(' ');
+            let next = chars.get(i + 1).copied().unwrap_or(' ');
+            let glued = matches!(prev, '(' | '[' | '<' | '&' | ':')
+                || matches!(next, ')' | ']' | '>' | ',' | ':')
+                || (next == '<' && (prev.is_alphanumeric() || prev == '_'));
+            if glued {
+                continue;
+            }
+        }
+        out.push(ch);
+    }
+
+    out
+}
+
+/// Lines of the doc comment, trimmed and joined by spaces.
+fn doc_comment(func: &ItemFn) -> String {
+    let lines: Vec<String> = func
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) => match &meta.value {
+                Expr::Lit(expr) => match &expr.lit {
+                    Lit::Str(line) => Some(line.value().trim().to_string()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    lines.join(" ")
 }
+
+#[cfg(test)]
+mod test;
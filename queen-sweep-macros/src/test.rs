@@ -0,0 +1,101 @@
+use quote::quote;
+use syn::ItemFn;
+
+use crate::{check_signature, parse_args};
+
+fn signature_error(func: proc_macro2::TokenStream) -> Option<String> {
+    let func: ItemFn = syn::parse2(func).unwrap();
+    check_signature(&func.sig).err().map(|err| err.to_string())
+}
+
+#[test]
+fn test_parse_args() {
+    let args = parse_args(quote! {
+        name = "by-region",
+        description = "Smallest regions first.",
+        tags = ["region", "static"],
+    })
+    .unwrap();
+
+    assert_eq!(args.name.unwrap().value(), "by-region");
+    assert_eq!(args.description.unwrap().value(), "Smallest regions first.");
+    let tags: Vec<String> = args.tags.unwrap().iter().map(|tag| tag.value()).collect();
+    assert_eq!(tags, ["region", "static"]);
+
+    let err = |attr| parse_args(attr).err().unwrap().to_string();
+    assert!(err(quote! { label = "x" }).starts_with("unknown heuristic argument"));
+    assert!(err(quote! { name = "By Region" }).starts_with("heuristic names are kebab-case"));
+    assert_eq!(
+        err(quote! { tags = [], tags = [] }),
+        "duplicate `tags` argument"
+    );
+    assert_eq!(
+        err(quote! { name = "a", name = "b" }),
+        "duplicate `name` argument"
+    );
+}
+
+#[test]
+fn test_check_signature() {
+    assert_eq!(
+        signature_error(quote! {
+            fn h(ctx: &HeuristicContext) -> Vec<((usize, usize), f32)> { todo!() }
+        }),
+        None
+    );
+    assert_eq!(
+        signature_error(quote! {
+            fn h(ctx: &mut HeuristicContext) -> Vec<((usize, usize), f32)> { todo!() }
+        })
+        .unwrap(),
+        "the context is shared, expected `&HeuristicContext`"
+    );
+    assert_eq!(
+        signature_error(quote! {
+            fn h(ctx: &GameState) -> Vec<((usize, usize), f32)> { todo!() }
+        })
+        .unwrap(),
+        "expected `&HeuristicContext`, found `&GameState`"
+    );
+    assert_eq!(
+        signature_error(quote! {
+            fn h(ctx: &HeuristicContext, depth: usize) -> Vec<((usize, usize), f32)> { todo!() }
+        })
+        .unwrap(),
+        "heuristics take a single `&HeuristicContext` parameter"
+    );
+    assert_eq!(
+        signature_error(quote! {
+            fn h(ctx: &HeuristicContext) -> Vec<(usize, f32)> { todo!() }
+        })
+        .unwrap(),
+        "expected `Vec<((usize, usize), f32)>`, found `Vec<(usize, f32)>`"
+    );
+    assert_eq!(
+        signature_error(quote! {
+            fn h(ctx: &HeuristicContext) -> std::vec::Vec<(usize, f32)> { todo!() }
+        })
+        .unwrap(),
+        "expected `Vec<((usize, usize), f32)>`, found `std::vec::Vec<(usize, f32)>`"
+    );
+}
+
+#[test]
+fn test_check_signature_accepts_paths_and_aliases() {
+    // aliases are left to the compiler, which checks the registered function pointer
+    for func in [
+        quote! { fn h(ctx: &HeuristicContext) -> std::vec::Vec<((usize, usize), f32)> { todo!() } },
+        quote! { fn h(ctx: &HeuristicContext) -> Scores { todo!() } },
+        quote! { fn h(ctx: &HeuristicContext) -> Vec<(Position, f32)> { todo!() } },
+    ] {
+        assert_eq!(signature_error(func), None);
+    }
+
+    assert_eq!(
+        signature_error(quote! {
+            fn h(ctx: &HeuristicContext) -> f32 { todo!() }
+        })
+        .unwrap(),
+        "expected `Vec<((usize, usize), f32)>`, found `f32`"
+    );
+}
@@ -1,7 +1,37 @@
-use js_sys::Uint8Array;
+use js_sys::{Uint8Array, Uint16Array};
 use wasm_bindgen::prelude::*;
 
-use queen_sweep_core::{GameState, depth_first_search, heuristic::*};
+use queen_sweep_core::{
+    GameState, SolveOptions, TranspositionConfig, TranspositionPolicy,
+    depth_first_search_with_options, heuristic::*,
+};
+
+/// Visited-state table budget of `solve`, so large boards cannot exhaust
+/// the browser's memory.
+const DEFAULT_TABLE_BYTES: usize = 64 * 1024 * 1024;
+
+/// How `solve_with_table` records visited states.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub enum TableMode {
+    Disabled,
+    Exact,
+    Hashes,
+    Lru,
+    DepthPreferred,
+}
+
+impl From<TableMode> for TranspositionPolicy {
+    fn from(mode: TableMode) -> Self {
+        match mode {
+            TableMode::Disabled => TranspositionPolicy::Disabled,
+            TableMode::Exact => TranspositionPolicy::Exact,
+            TableMode::Hashes => TranspositionPolicy::Hashes,
+            TableMode::Lru => TranspositionPolicy::Lru,
+            TableMode::DepthPreferred => TranspositionPolicy::DepthPreferred,
+        }
+    }
+}
 
 #[wasm_bindgen]
 pub struct QueensGame(GameState);
@@ -18,21 +48,58 @@ impl QueensGame {
         Ok(QueensGame(inner))
     }
 
+    /// Builds a board from `u16` region ids, for layouts with more than 255 regions.
+    #[wasm_bindgen]
+    pub fn from_wide_color_regions(color_regions: Vec<Uint16Array>) -> Result<QueensGame, JsValue> {
+        let regions: Vec<Vec<u16>> = color_regions.iter().map(|arr| arr.to_vec()).collect();
+
+        let inner = GameState::from_color_regions(regions, Some(smallest_region_by_empty_cells))
+            .map_err(|e| JsError::new(&e.to_string()))?;
+
+        Ok(QueensGame(inner))
+    }
+
+    /// Switches to the registered heuristic called `name`.
+    #[wasm_bindgen]
+    pub fn set_heuristic(&mut self, name: &str) -> Result<(), JsValue> {
+        let info = find(name).ok_or_else(|| JsError::new(&format!("unknown heuristic: {name}")))?;
+        self.0 = self.0.clone().with_heuristic(info.func);
+        Ok(())
+    }
+
     #[wasm_bindgen]
     pub fn solve(&self) -> Option<QueensGame> {
-        let (solution_opt, _steps) = depth_first_search(self.0.clone());
-        solution_opt.map(QueensGame)
+        self.solve_with_table(TableMode::Exact, Some(DEFAULT_TABLE_BYTES))
     }
 
+    /// Solves with the given visited-state table. Without `max_bytes` the
+    /// table grows with the search.
     #[wasm_bindgen]
-    pub fn get_queen_positions(&self) -> Vec<Uint8Array> {
-        let positions = self.0.queen_positions();
+    pub fn solve_with_table(
+        &self,
+        mode: TableMode,
+        max_bytes: Option<usize>,
+    ) -> Option<QueensGame> {
+        let options = SolveOptions {
+            transposition: TranspositionConfig {
+                policy: mode.into(),
+                max_bytes,
+            },
+            ..SolveOptions::default()
+        };
+
+        let (solution_opt, _stats) = depth_first_search_with_options(self.0.clone(), &options);
+        solution_opt.map(QueensGame)
+    }
 
-        positions
-            .into_iter()
+    /// Queen positions as `u16` pairs, so boards wider than 255 cells fit.
+    #[wasm_bindgen]
+    pub fn get_queen_positions(&self) -> Vec<Uint16Array> {
+        self.0
+            .queen_positions()
             .map(|(r, c)| {
-                let arr = vec![r as u8, c as u8];
-                Uint8Array::from(arr.as_slice())
+                let arr = vec![r as u16, c as u16];
+                Uint16Array::from(arr.as_slice())
             })
             .collect()
     }
@@ -44,12 +111,12 @@ impl QueensGame {
 
     #[wasm_bindgen]
     pub fn get_states_2d(&self) -> Vec<Uint8Array> {
-        let size = self.0.size();
+        let (num_rows, num_cols) = (self.0.rows(), self.0.cols());
 
-        let mut rows = Vec::with_capacity(size);
-        for r in 0..size {
-            let start = r * size;
-            let end = start + size;
+        let mut rows = Vec::with_capacity(num_rows);
+        for r in 0..num_rows {
+            let start = r * num_cols;
+            let end = start + num_cols;
             let row = self.0.states()[start..end]
                 .iter()
                 .map(|&s| s as u8)
@@ -61,3 +128,26 @@ impl QueensGame {
         rows
     }
 }
+
+/// Names of the registered heuristics, for `QueensGame::set_heuristic`.
+#[wasm_bindgen]
+pub fn heuristic_names() -> Vec<String> {
+    registry()
+        .iter()
+        .map(|info| info.name.to_string())
+        .collect()
+}
+
+/// Doc comment of the registered heuristic called `name`.
+#[wasm_bindgen]
+pub fn heuristic_description(name: &str) -> Option<String> {
+    find(name).map(|info| info.description.to_string())
+}
+
+/// Tags of the registered heuristic called `name`.
+#[wasm_bindgen]
+pub fn heuristic_tags(name: &str) -> Vec<String> {
+    find(name).map_or_else(Vec::new, |info| {
+        info.tags.iter().map(|tag| tag.to_string()).collect()
+    })
+}